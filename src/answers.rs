@@ -0,0 +1,149 @@
+use crate::solver::Part;
+use std::{collections::HashMap, fs, io, path, time};
+
+// Answers accepted on the Advent of Code website are recorded by hand in
+// `input/answers/<year>.txt`, one puzzle per line, e.g. `05b 46`
+#[derive(Debug, Default)]
+pub struct Answers {
+    records: HashMap<(usize, Part), usize>,
+}
+
+impl Answers {
+    pub fn load(year: usize) -> Self {
+        // Having no records yet is not an error
+        fs::read_to_string(answers_path(&format!("{year}.txt")))
+            .map(|records| Self::parse(&records))
+            .unwrap_or_default()
+    }
+
+    fn parse(records: &str) -> Self {
+        Self {
+            records: parse_records(records),
+        }
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<usize> {
+        self.records.get(&(day, part)).copied()
+    }
+
+    // The number of stars earned on the given day
+    pub fn stars(&self, day: usize) -> usize {
+        [Part::One, Part::Two]
+            .iter()
+            .filter(|part| self.get(day, **part).is_some())
+            .count()
+    }
+}
+
+// The time each puzzle last took in the dashboard, kept next to the answers in
+// `input/answers/<year>.timings.txt` as nanoseconds, e.g. `05b 46000`
+#[derive(Debug, Default)]
+pub struct Timings {
+    path: path::PathBuf,
+    records: HashMap<(usize, Part), time::Duration>,
+}
+
+impl Timings {
+    pub fn load(year: usize) -> Self {
+        Self::load_from(answers_path(&format!("{year}.timings.txt")))
+    }
+
+    fn load_from(path: path::PathBuf) -> Self {
+        let records = fs::read_to_string(&path)
+            .map(|records| parse_records(&records))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(puzzle, nanos)| (puzzle, time::Duration::from_nanos(nanos)))
+            .collect();
+        Self { path, records }
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<time::Duration> {
+        self.records.get(&(day, part)).copied()
+    }
+
+    // Record a timing and write all of them out straight away, as the
+    // dashboard may be closed at any moment
+    pub fn insert(&mut self, day: usize, part: Part, duration: time::Duration) -> io::Result<()> {
+        self.records.insert((day, part), duration);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut lines = self
+            .records
+            .iter()
+            .map(|((day, part), duration)| {
+                let part = match part {
+                    Part::One => 'a',
+                    Part::Two => 'b',
+                };
+                format!("{day:02}{part} {}\n", duration.as_nanos())
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        fs::write(&self.path, lines.concat())
+    }
+}
+
+fn answers_path(file: &str) -> path::PathBuf {
+    [".", "input", "answers", file].iter().collect()
+}
+
+// Lines of a puzzle and a number, skipping any that are not
+fn parse_records<T: std::str::FromStr>(records: &str) -> HashMap<(usize, Part), T> {
+    records
+        .lines()
+        .filter_map(|line| {
+            let (puzzle, value) = line.split_once(' ')?;
+            let part = match puzzle.get(2..)? {
+                "a" => Part::One,
+                "b" => Part::Two,
+                _ => return None,
+            };
+            let day = puzzle.get(..2)?.parse().ok()?;
+            let value = value.trim().parse().ok()?;
+            Some(((day, part), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_records() {
+        let answers = Answers::parse("01a 142\n01b 281\n05a 35\nfoo\n06c 1\n");
+        assert_eq!(answers.get(1, Part::Two), Some(281));
+        assert_eq!(answers.get(5, Part::Two), None);
+        assert_eq!(answers.stars(1), 2);
+        assert_eq!(answers.stars(5), 1);
+        assert_eq!(answers.records.len(), 3);
+    }
+
+    #[test]
+    fn timings_round_trip() {
+        let path = std::env::temp_dir().join(format!("timings-{}.txt", std::process::id()));
+        let mut timings = Timings::load_from(path.clone());
+        assert_eq!(timings.get(5, Part::Two), None);
+        timings
+            .insert(5, Part::Two, time::Duration::from_nanos(46_000))
+            .unwrap();
+        timings
+            .insert(1, Part::One, time::Duration::from_micros(7))
+            .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "01a 7000\n05b 46000\n");
+
+        // a restarted dashboard sees the same timings
+        let timings = Timings::load_from(path.clone());
+        assert_eq!(
+            timings.get(5, Part::Two),
+            Some(time::Duration::from_nanos(46_000))
+        );
+        assert_eq!(
+            timings.get(1, Part::One),
+            Some(time::Duration::from_micros(7))
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,202 @@
+use crate::solver::{Puzzle, PuzzleOutcome};
+use std::{collections::HashMap, env, fs, io, path, sync::OnceLock, time};
+use tracing::{debug, warn};
+
+// The answers of earlier runs, keyed by puzzle, e.g. `2023/05a`
+pub struct Cache {
+    path: path::PathBuf,
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    input_hash: u64,
+    build_hash: u64,
+    solution: usize,
+    duration: time::Duration,
+}
+
+impl Cache {
+    // Load the cache, starting over if it is missing or unreadable
+    pub fn load(path: &path::Path) -> Self {
+        let entries = fs::read_to_string(path)
+            .map(|contents| contents.lines().filter_map(parse_line).collect())
+            .unwrap_or_default();
+        Self {
+            path: path.to_path_buf(),
+            entries,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut lines = self
+            .entries
+            .iter()
+            .map(|(puzzle, entry)| {
+                format!(
+                    "{puzzle} {:016x} {:016x} {} {}\n",
+                    entry.input_hash,
+                    entry.build_hash,
+                    entry.solution,
+                    entry.duration.as_nanos()
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        fs::write(&self.path, lines.concat())
+    }
+
+    // Return the cached answer if neither the input nor the build changed
+    // since it was stored, and solve the puzzle otherwise
+    pub fn load_and_solve(&mut self, puzzle: &Puzzle) -> Result<PuzzleOutcome, io::Error> {
+        let input = puzzle.load_input()?;
+        // Without a build hash a changed solver cannot be told apart, so the
+        // cache is neither read nor written
+        let Some(build_hash) = build_hash() else {
+            return Ok(puzzle.solve(&input));
+        };
+        let input_hash = fnv1a(input.as_bytes());
+        let key = puzzle.to_string();
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.input_hash == input_hash && entry.build_hash == build_hash {
+                debug!(solution = entry.solution, "using cached answer");
+                return Ok(PuzzleOutcome::Cached {
+                    solution: entry.solution,
+                    duration: entry.duration,
+                });
+            }
+        }
+
+        // Only store solutions, so errors are retried on the next run
+        let outcome = puzzle.solve(&input);
+        if let PuzzleOutcome::Solved { solution, duration } = outcome {
+            let entry = Entry {
+                input_hash,
+                build_hash,
+                solution,
+                duration,
+            };
+            self.entries.insert(key, entry);
+        }
+        Ok(outcome)
+    }
+}
+
+// Whether `AOC_CACHE` is set to one of the usual spellings of true
+pub fn enabled_by_env() -> bool {
+    let Ok(value) = env::var("AOC_CACHE") else {
+        return false;
+    };
+    parse_flag(&value).unwrap_or_else(|| {
+        warn!(value, "ignoring AOC_CACHE, which is not a boolean");
+        false
+    })
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "" | "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_line(line: &str) -> Option<(String, Entry)> {
+    let mut fields = line.split_whitespace();
+    let puzzle = fields.next()?.to_string();
+    let entry = Entry {
+        input_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+        build_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+        solution: fields.next()?.parse().ok()?,
+        duration: time::Duration::from_nanos(fields.next()?.parse().ok()?),
+    };
+    Some((puzzle, entry))
+}
+
+// Any change to a solver changes the executable, so hash that instead of the
+// sources, which may not be around at runtime
+fn build_hash() -> Option<u64> {
+    static BUILD_HASH: OnceLock<Option<u64>> = OnceLock::new();
+    *BUILD_HASH.get_or_init(|| match env::current_exe().and_then(fs::read) {
+        Ok(bytes) => Some(fnv1a(&bytes)),
+        Err(error) => {
+            warn!(%error, "not caching answers, as the executable cannot be read");
+            None
+        }
+    })
+}
+
+// 64-bit FNV-1a, which is stable across runs unlike the standard hasher
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(fnv1a(b"ab"), fnv1a(b"ba"));
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let path = env::temp_dir().join(format!("cache-{}.txt", std::process::id()));
+        let entry = Entry {
+            input_hash: 1,
+            build_hash: u64::MAX,
+            solution: 42,
+            duration: time::Duration::from_nanos(1234),
+        };
+        let mut cache = Cache::load(&path);
+        assert!(cache.entries.is_empty());
+        cache.entries.insert("2023/05a".to_string(), entry.clone());
+        cache.save().unwrap();
+
+        let cache = Cache::load(&path);
+        assert_eq!(cache.entries.get("2023/05a"), Some(&entry));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn flags() {
+        for value in ["1", "true", "TRUE", "yes", "on "] {
+            assert_eq!(parse_flag(value), Some(true), "{value:?}");
+        }
+        for value in ["", "0", "false", "False", "no", "off"] {
+            assert_eq!(parse_flag(value), Some(false), "{value:?}");
+        }
+        assert_eq!(parse_flag("2"), None);
+    }
+
+    #[test]
+    fn stale_entries_are_ignored() {
+        let path = env::temp_dir().join(format!("stale-{}.txt", std::process::id()));
+        let puzzle = Puzzle::new(2023, 1, crate::solver::Part::One);
+        let Ok(input) = puzzle.load_input() else {
+            return;
+        };
+        let mut cache = Cache::load(&path);
+        let entry = Entry {
+            input_hash: fnv1a(input.as_bytes()),
+            build_hash: build_hash().unwrap().wrapping_add(1),
+            solution: 0,
+            duration: time::Duration::ZERO,
+        };
+        cache.entries.insert(puzzle.to_string(), entry);
+        let outcome = cache.load_and_solve(&puzzle).unwrap();
+        assert!(matches!(outcome, PuzzleOutcome::Solved { .. }));
+        assert!(matches!(
+            cache.load_and_solve(&puzzle).unwrap(),
+            PuzzleOutcome::Cached { solution, .. } if Some(solution) == outcome.solution()
+        ));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::solver::{Part, Puzzle, Solver};
+use itertools::Itertools;
+
+// Inputs are generated with a growing size parameter, so the first failure
+// found is usually small already and shrinking has little left to remove
+const CASES: u64 = 100;
+const MAX_SIZE: usize = 20;
+
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+// Small splitmix64 generator, good enough for test input generation
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniformly pick a number from the half-open range `lo..hi`
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        assert!(lo < hi);
+        lo + (self.next_u64() % (hi - lo) as u64) as usize
+    }
+}
+
+pub fn differential(generate: Generator, fast: Solver, reference: Solver) {
+    let fails = |input: &str| fast(input) != reference(input);
+    let failing = (0..CASES).find_map(|case| {
+        let size = case as usize * MAX_SIZE / CASES as usize;
+        Some(generate(&mut Rng::new(case), size)).filter(|input| fails(input))
+    });
+
+    if let Some(input) = failing {
+        let input = shrink(input, fails);
+        panic!(
+            "fast solver returned {:?} but reference returned {:?} for input:\n{input}",
+            fast(&input),
+            reference(&input)
+        );
+    }
+}
+
+// Remove blocks, lines and words from a failing input for as long as it keeps
+// failing, so the reported input only contains what the failure depends on
+fn shrink(mut input: String, fails: impl Fn(&str) -> bool) -> String {
+    loop {
+        let mut candidates = smaller(&input).into_iter();
+        match candidates.find(|smaller| smaller.len() < input.len() && fails(smaller)) {
+            Some(smaller) => input = smaller,
+            None => return input,
+        }
+    }
+}
+
+// Every input with one block, line, pair of words or word left out, trying the
+// biggest cuts first
+fn smaller(input: &str) -> Vec<String> {
+    let without = |parts: &[&str], i: usize, len: usize, separator: &str| {
+        parts[..i].iter().chain(&parts[i + len..]).join(separator)
+    };
+    let mut candidates = Vec::new();
+    let blocks = input.split("\n\n").collect::<Vec<_>>();
+    if blocks.len() > 1 {
+        candidates.extend((0..blocks.len()).map(|i| without(&blocks, i, 1, "\n\n")));
+    }
+    let lines = input.split('\n').collect::<Vec<_>>();
+    if lines.len() > 1 {
+        candidates.extend((0..lines.len()).map(|i| without(&lines, i, 1, "\n")));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        let words = line.split(' ').collect::<Vec<_>>();
+        for len in [2, 1] {
+            for j in 0..(words.len() + 1).saturating_sub(len) {
+                let line = without(&words, j, len, " ");
+                let mut shorter = lines.clone();
+                shorter[i] = &line;
+                candidates.push(shorter.join("\n"));
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solver::Error;
+
+    #[test]
+    fn reference_solvers() {
+        for day in 1..=25 {
+            for part in [Part::One, Part::Two] {
+                let puzzle = Puzzle::new(2023, day, part);
+                if let (Some(generate), Some(fast), Some(reference)) = (
+                    puzzle.retrieve_generator(),
+                    puzzle.retrieve_solver(),
+                    puzzle.retrieve_reference(),
+                ) {
+                    differential(generate, fast, reference);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "for input:\n7")]
+    fn reports_minimal_input() {
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..=size)
+                .map(|_| rng.range(0, 10).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        fn fast(input: &str) -> Result<usize, Error> {
+            Ok(input.lines().filter(|line| *line != "7").count())
+        }
+        fn reference(input: &str) -> Result<usize, Error> {
+            Ok(input.lines().count())
+        }
+        differential(generate, fast, reference);
+    }
+
+    #[test]
+    fn shrink_words_lines_and_blocks() {
+        let input = "seeds: 1 2 13 4\n5 6\n\nmap:\n7 13 8\n9".to_string();
+        let fails = |input: &str| input.split_whitespace().any(|word| word == "13");
+        assert_eq!(shrink(input, fails), "13");
+        let fails = |input: &str| input.contains("\n\n");
+        assert_eq!(shrink("a\nb c\n\nd\n\ne".to_string(), fails), "\n\n");
+    }
+}
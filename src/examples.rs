@@ -0,0 +1,96 @@
+use crate::solver::{Part, Puzzle};
+use std::path;
+
+pub struct Example {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+    pub file: &'static str,
+    pub answer: usize,
+}
+
+impl Example {
+    pub fn path(&self) -> path::PathBuf {
+        [
+            ".",
+            "input",
+            "test",
+            self.year.to_string().as_str(),
+            self.file,
+        ]
+        .iter()
+        .collect()
+    }
+
+    pub fn puzzle(&self) -> Puzzle {
+        Puzzle::new(self.year, self.day, self.part)
+    }
+}
+
+// Every entry in the manifest becomes both an element of `EXAMPLES` and a
+// test case with the given name, so adding an example only requires a new line
+macro_rules! examples {
+    ($($name:ident: $year:literal $day:literal $part:ident $file:literal => $answer:literal,)*) => {
+        pub const EXAMPLES: &[Example] = &[
+            $(Example {
+                year: $year,
+                day: $day,
+                part: Part::$part,
+                file: $file,
+                answer: $answer,
+            },)*
+        ];
+
+        #[cfg(test)]
+        mod test {
+            use super::*;
+
+            examples!(@tests 0; $($name)*);
+        }
+    };
+
+    // Each test checks its own element of `EXAMPLES`, so the two cannot drift
+    (@tests $index:expr; $name:ident $($rest:ident)*) => {
+        #[test]
+        fn $name() {
+            check(&EXAMPLES[$index]);
+        }
+
+        examples!(@tests $index + 1; $($rest)*);
+    };
+    (@tests $index:expr;) => {};
+}
+
+examples! {
+    y2023_day01_part1: 2023 1 One "day01a.txt" => 142,
+    y2023_day01_part2: 2023 1 Two "day01b.txt" => 281,
+    y2023_day02_part1: 2023 2 One "day02.txt" => 8,
+    y2023_day02_part2: 2023 2 Two "day02.txt" => 2286,
+    y2023_day03_part1: 2023 3 One "day03.txt" => 4361,
+    y2023_day03_part2: 2023 3 Two "day03.txt" => 467835,
+    y2023_day04_part1: 2023 4 One "day04.txt" => 13,
+    y2023_day04_part2: 2023 4 Two "day04.txt" => 30,
+    y2023_day05_part1: 2023 5 One "day05.txt" => 35,
+    y2023_day05_part2: 2023 5 Two "day05.txt" => 46,
+    y2023_day06_part1: 2023 6 One "day06.txt" => 288,
+    y2023_day06_part2: 2023 6 Two "day06.txt" => 71503,
+    y2023_day07_part1: 2023 7 One "day07.txt" => 6440,
+    y2023_day07_part2: 2023 7 Two "day07.txt" => 5905,
+}
+
+#[cfg(test)]
+fn check(example: &Example) {
+    let outcome = example
+        .puzzle()
+        .load_and_solve_from(&example.path())
+        .unwrap();
+    assert_eq!(outcome.solution(), Some(example.answer));
+}
+
+#[cfg(test)]
+#[test]
+fn manifest_files_exist() {
+    for example in EXAMPLES {
+        assert!(example.path().is_file(), "missing {:?}", example.path());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::check::Rng;
+use crate::examples::EXAMPLES;
+use crate::solver::Solver;
+use std::{env, fs, panic, sync::mpsc, thread, time};
+
+// The number of inputs per solver can be raised for longer runs with the
+// `FUZZ_ITERATIONS` environment variable
+const ITERATIONS: u64 = 200;
+const MAX_MUTATIONS: usize = 3;
+const TIMEOUT: time::Duration = time::Duration::from_secs(10);
+const ALPHABET: &[char] = &[
+    '0', '1', '2', '5', '9', ' ', ' ', '\n', '\r', ':', ';', ',', '|', '-', '*', '#', '.', 'a',
+    'e', 'o', 'z', 'A', 'J', 'K', 'T', 'µ',
+];
+
+fn iterations() -> u64 {
+    env::var("FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(ITERATIONS)
+}
+
+fn random_input(rng: &mut Rng) -> String {
+    let len = rng.range(0, 200);
+    (0..len)
+        .map(|_| ALPHABET[rng.range(0, ALPHABET.len())])
+        .collect()
+}
+
+fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    for _ in 0..rng.range(1, MAX_MUTATIONS + 1) {
+        let random_char = ALPHABET[rng.range(0, ALPHABET.len())];
+        match (rng.range(0, 5), chars.len()) {
+            (0, 1..) => {
+                chars.remove(rng.range(0, chars.len()));
+            }
+            (1, _) => chars.insert(rng.range(0, chars.len() + 1), random_char),
+            (2, 1..) => {
+                let i = rng.range(0, chars.len());
+                chars[i] = random_char;
+            }
+            (3 | 4, _) => {
+                // duplicate or delete a whole line
+                let mut lines = chars
+                    .split(|char| *char == '\n')
+                    .map(|line| line.to_vec())
+                    .collect::<Vec<_>>();
+                let i = rng.range(0, lines.len());
+                if rng.range(0, 2) == 0 {
+                    lines.insert(i, lines[i].clone());
+                } else {
+                    lines.remove(i);
+                }
+                chars = lines.join(&'\n');
+            }
+            _ => (),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// Run the solver on a separate thread, so panics and hangs can be reported
+fn run(solver: Solver, input: &str) -> Result<(), &'static str> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    thread::spawn(move || {
+        let outcome = panic::catch_unwind(|| solver(&input));
+        sender.send(outcome.is_ok()).ok();
+    });
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("panicked"),
+        Err(_) => Err("timed out"),
+    }
+}
+
+#[test]
+fn solvers_survive_arbitrary_inputs() {
+    let mut failures = Vec::new();
+    for (n, example) in EXAMPLES.iter().enumerate() {
+        let seed = fs::read_to_string(example.path()).unwrap();
+        let puzzle = example.puzzle();
+        let solver = puzzle.retrieve_solver().unwrap();
+        let mut rng = Rng::new(n as u64);
+        for i in 0..iterations() {
+            let input = if i % 4 == 0 {
+                random_input(&mut rng)
+            } else {
+                mutate(&mut rng, &seed)
+            };
+            if let Err(reason) = run(solver, &input) {
+                failures.push(format!("{puzzle} {reason} on input:\n{input}"));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
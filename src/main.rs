@@ -1,53 +1,290 @@
-use clap::Parser;
-use std::time;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{io, path, process::ExitCode, time};
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+mod answers;
+mod cache;
+#[cfg(test)]
+mod check;
+mod examples;
+#[cfg(test)]
+mod fuzz;
+mod profile;
+mod progress;
+mod repl;
+mod report;
 mod solver;
+mod tui;
+mod util;
+mod visual;
 mod year2023;
-use solver::{Part, Puzzle};
+use answers::Answers;
+use solver::{Error, Part, Puzzle, PuzzleOutcome};
 
-fn main() {
+// Exit codes, of which the highest that applies to any puzzle is used, where
+// `EXIT_NOTHING_SOLVED` means not a single puzzle of the run got an answer,
+// e.g. because the year has no solvers or the inputs are missing
+const EXIT_FAILED: u8 = 1;
+const EXIT_TIMED_OUT: u8 = 3;
+const EXIT_WRONG_ANSWER: u8 = 4;
+const EXIT_NOTHING_SOLVED: u8 = 5;
+
+fn main() -> ExitCode {
     // Parse the input arguments
     let args = Args::parse();
-    let year = args.year;
+
+    // Logging to stderr would draw over the dashboard, so leave it off there
+    if !matches!(args.command, Some(Command::Tui { .. })) {
+        init_tracing(args.verbose);
+    }
+    match args.command {
+        Some(Command::Tui { year }) => {
+            if let Err(error) = tui::run(year) {
+                eprintln!("Could not run the dashboard: {error}");
+                return ExitCode::from(EXIT_FAILED);
+            }
+            return ExitCode::SUCCESS;
+        }
+        Some(Command::Readme {
+            year,
+            runs,
+            update,
+            svg,
+        }) => {
+            if let Err(error) = progress::run(year, runs, update.as_deref(), svg.as_deref()) {
+                eprintln!("Could not write the progress table: {error}");
+                return ExitCode::from(EXIT_FAILED);
+            }
+            return ExitCode::SUCCESS;
+        }
+        Some(Command::Repl { year, day }) => {
+            if let Err(error) = repl::run(year, day) {
+                eprintln!("Could not run the REPL: {error}");
+                return ExitCode::from(EXIT_FAILED);
+            }
+            return ExitCode::SUCCESS;
+        }
+        None => (),
+    }
+    let year = args
+        .year
+        .expect("clap requires a year without a subcommand");
     let days: Vec<_> = match args.day {
         Some(day) => vec![day],
         None => (1..=25).collect(),
     };
 
-    // Keep track of the total computation time
-    let mut total_duration = time::Duration::default();
+    // Reuse earlier answers when asked to, unless explicitly bypassed
+    let use_cache = (args.cache || cache::enabled_by_env()) && !args.no_cache;
+    let mut cache = use_cache.then(|| cache::Cache::load(&args.cache_file));
 
-    // Solve both puzzles for each day
-    for day in days.into_iter() {
-        for part in [Part::One, Part::Two] {
+    // Collect the outcomes of both puzzles for each day
+    let answers = Answers::load(year);
+    let mut exit_code = 0;
+    let mut report = report::Report::new(year);
+    let mut flamegraphs = Vec::new();
+    let mut solved = false;
+    for day in days.iter().copied() {
+        // Leave out days that have not been implemented when running a whole year
+        let implemented = [Part::One, Part::Two]
+            .into_iter()
+            .any(|part| Puzzle::new(year, day, part).retrieve_solver().is_some());
+        if !implemented && days.len() > 1 {
+            continue;
+        }
+
+        let results = [Part::One, Part::Two].map(|part| {
             // Initialise the puzzle and solve it
-            let puzzle = Puzzle::new(year, day, part);
-            let outcome = puzzle.load_and_solve().unwrap_or_default();
+            let puzzle = Puzzle::new(year, day, part)
+                .with_timeout(args.timeout.map(time::Duration::from_secs_f64));
+            let _span = puzzle.span().entered();
+            match args.visualise {
+                Some(Visualise::Play) => visual::install(visual::Sink::Play { fps: args.fps }),
+                Some(Visualise::Dump) => visual::install(visual::Sink::Dump {
+                    directory: args.reports.join("frames").join(puzzle.to_string()),
+                    count: 0,
+                }),
+                None => (),
+            }
+            let result = match &mut cache {
+                Some(cache) => cache.load_and_solve(&puzzle),
+                None => puzzle.load_and_solve(),
+            };
+            visual::uninstall();
 
-            // Do some logging
-            println!("{puzzle}: {outcome}");
+            // Sample the solver separately, so profiling does not affect the timings
+            if args.profile
+                && matches!(&result, Ok(outcome) if !matches!(outcome, PuzzleOutcome::Failed))
+            {
+                flamegraphs.push((
+                    puzzle.to_string(),
+                    profile::profile(&puzzle, &args.reports.join("profile")),
+                ));
+            }
+            result
+        });
 
-            // Keep track of the total computation time
-            total_duration += outcome.duration();
+        for (part, result) in [Part::One, Part::Two].into_iter().zip(&results) {
+            let puzzle = Puzzle::new(year, day, part);
+            let outcome = match result {
+                Ok(outcome) => outcome,
+                Err(error) => {
+                    eprintln!("{puzzle}: could not read the input ({error})");
+                    exit_code = exit_code.max(EXIT_FAILED);
+                    continue;
+                }
+            };
+            let code = match (outcome.solution(), answers.get(day, part), outcome) {
+                (Some(solution), Some(answer), _) if solution != answer => {
+                    eprintln!("{puzzle}: expected {answer}, got {solution}");
+                    EXIT_WRONG_ANSWER
+                }
+                (Some(_), _, _) => 0,
+                (_, _, PuzzleOutcome::Errored { error, .. }) if *error == Error::Timeout => {
+                    EXIT_TIMED_OUT
+                }
+                _ => EXIT_FAILED,
+            };
+            match outcome.solution() {
+                Some(solution) if args.quiet => println!("{solution}"),
+                None if args.quiet => eprintln!("{puzzle}: {outcome}"),
+                _ => (),
+            }
+            solved |= outcome.solution().is_some();
+            exit_code = exit_code.max(code);
         }
+        report.push(day, results.map(Result::unwrap_or_default));
+    }
+    if !solved {
+        eprintln!("No puzzle of {year} was solved");
+        exit_code = exit_code.max(EXIT_NOTHING_SOLVED);
     }
 
-    // Print the total computation time
-    let total_duration_string = duration_to_string(total_duration);
-    println!();
-    println!("Total computation time: {total_duration_string}");
-    println!();
+    if let Some(Err(error)) = cache.map(|cache| cache.save()) {
+        eprintln!("Could not save the result cache: {error}");
+    }
+
+    // Print the formatted report, unless only the answers were asked for
+    if !args.quiet {
+        println!("{report}");
+    }
+    // Keep stdout for the answers, so it can be piped even when profiling
+    for (puzzle, flamegraph) in flamegraphs {
+        match flamegraph {
+            Ok(path) => eprintln!("{puzzle}: flamegraph written to {}", path.display()),
+            Err(error) => eprintln!("{puzzle}: could not profile ({error})"),
+        }
+    }
+    ExitCode::from(exit_code)
 }
 
 // Define expected command line arguments
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     // Set the year
-    year: usize,
+    #[arg(required = true)]
+    year: Option<usize>,
 
     // Set the day
     // If no day is set, all days will be run
     day: Option<usize>,
+
+    // Sample each solver and write a flamegraph and collapsed stacks per puzzle
+    #[arg(long)]
+    profile: bool,
+
+    // Set the directory to write reports such as flamegraphs to
+    #[arg(long, default_value = "reports")]
+    reports: path::PathBuf,
+
+    // Replay the frames emitted by solvers, or dump them to the reports directory
+    #[arg(long, value_enum)]
+    visualise: Option<Visualise>,
+
+    // Set the frame rate for replaying frames
+    #[arg(long, default_value_t = 10)]
+    fps: u32,
+
+    // Reuse answers of earlier runs if neither the input nor the build changed,
+    // which can also be enabled by setting AOC_CACHE=1
+    #[arg(long)]
+    cache: bool,
+
+    // Solve every puzzle again, even if caching is enabled
+    #[arg(long, conflicts_with = "cache")]
+    no_cache: bool,
+
+    // Log what solvers are doing to stderr, with -vv also timing every span
+    // RUST_LOG takes precedence, e.g. RUST_LOG=advent_of_code::year2023::day05=trace
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    // Only print the answers, one per line, leaving failures to the exit code
+    #[arg(short, long)]
+    quiet: bool,
+
+    // Give up on a puzzle after this many seconds
+    #[arg(long, conflicts_with = "visualise")]
+    timeout: Option<f64>,
+
+    // Set the file to store cached answers in
+    #[arg(long, default_value = ".cache/results.txt")]
+    cache_file: path::PathBuf,
+}
+
+#[derive(ValueEnum, Copy, Clone, Debug)]
+enum Visualise {
+    Play,
+    Dump,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    // Browse the advent calendar of a year in an interactive dashboard
+    Tui {
+        year: usize,
+    },
+
+    // Explore the parsed input of a puzzle interactively
+    Repl {
+        year: usize,
+        day: usize,
+    },
+
+    // Generate a markdown progress table with stars and median runtimes
+    Readme {
+        year: usize,
+
+        // Set the number of runs per puzzle to take the median runtime of
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+
+        // Rewrite the marked progress region of this markdown file
+        #[arg(long)]
+        update: Option<path::PathBuf>,
+
+        // Also draw the star calendar as an SVG image at this path
+        #[arg(long)]
+        svg: Option<path::PathBuf>,
+    },
+}
+
+fn init_tracing(verbose: u8) {
+    let (level, span_events) = match verbose {
+        0 => ("warn", FmtSpan::NONE),
+        1 => ("debug", FmtSpan::NONE),
+        _ => ("trace", FmtSpan::CLOSE),
+    };
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(span_events)
+        .with_writer(io::stderr)
+        .init();
 }
 
 fn duration_to_string(duration: time::Duration) -> String {
@@ -0,0 +1,91 @@
+use crate::solver::Puzzle;
+#[cfg(target_os = "linux")]
+use std::{fs, time};
+use std::{io, path};
+
+// Sampling at 1 kHz for at least a second gives about a thousand samples
+#[cfg(target_os = "linux")]
+const FREQUENCY: i32 = 1000;
+#[cfg(target_os = "linux")]
+const MIN_DURATION: time::Duration = time::Duration::from_secs(1);
+
+// Profile the solver of the puzzle, repeating it until enough samples have been
+// collected, and write a flamegraph and collapsed stacks to the given directory
+#[cfg(target_os = "linux")]
+pub fn profile(puzzle: &Puzzle, directory: &path::Path) -> io::Result<path::PathBuf> {
+    let input = puzzle.load_input()?;
+
+    // The profiler uses a `SIGPROF` timer, which does not require root
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(io::Error::other)?;
+    let start_instant = time::Instant::now();
+    while start_instant.elapsed() < MIN_DURATION {
+        puzzle.solve(&input);
+    }
+    let report = guard.report().build().map_err(io::Error::other)?;
+
+    let path = report_path(puzzle, directory);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let flamegraph = fs::File::create(path.with_extension("svg"))?;
+    report.flamegraph(flamegraph).map_err(io::Error::other)?;
+    fs::write(path.with_extension("folded"), collapse(&report))?;
+    Ok(path.with_extension("svg"))
+}
+
+// The reports are written as `<directory>/<year>/<day><part>.{svg,folded}`
+#[cfg(target_os = "linux")]
+fn report_path(puzzle: &Puzzle, directory: &path::Path) -> path::PathBuf {
+    directory.join(puzzle.to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn profile(_puzzle: &Puzzle, _directory: &path::Path) -> io::Result<path::PathBuf> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "profiling is only supported on Linux",
+    ))
+}
+
+// Write every sampled stack on a line with its count, outermost frame first
+#[cfg(target_os = "linux")]
+fn collapse(report: &pprof::Report) -> String {
+    let mut lines = report
+        .data
+        .iter()
+        .map(|(frames, count)| {
+            let stack = frames
+                .frames
+                .iter()
+                .rev()
+                .flat_map(|frame| frame.iter().rev())
+                .map(|symbol| symbol.to_string())
+                .collect::<Vec<_>>();
+            let thread = frames.thread_name_or_id();
+            format!("{thread};{} {count}\n", stack.join(";"))
+        })
+        .collect::<Vec<_>>();
+    lines.sort();
+    lines.concat()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::*;
+    use crate::solver::Part;
+
+    #[test]
+    fn reports_per_puzzle() {
+        let puzzle = Puzzle::new(2023, 5, Part::Two);
+        let path = report_path(&puzzle, path::Path::new("reports/profile"));
+        assert_eq!(path, path::Path::new("reports/profile/2023/05b"));
+        assert_eq!(
+            path.with_extension("svg"),
+            path::Path::new("reports/profile/2023/05b.svg")
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use crate::answers::Answers;
+use crate::duration_to_string;
+use crate::solver::{Part, Puzzle, PuzzleOutcome};
+use std::{fmt::Write, fs, io, path, time};
+
+const START_MARKER: &str = "<!-- progress:start -->";
+const END_MARKER: &str = "<!-- progress:end -->";
+
+pub struct Progress {
+    year: usize,
+    days: Vec<DayProgress>,
+}
+
+struct DayProgress {
+    day: usize,
+    stars: usize,
+    // Whether the solution matches the recorded answer, if there is one
+    verified: [Option<bool>; 2],
+    medians: [Option<time::Duration>; 2],
+}
+
+// Write the progress of a year to stdout, or into the marked region of an
+// existing markdown file, and optionally draw the calendar as an SVG
+pub fn run(
+    year: usize,
+    runs: usize,
+    update: Option<&path::Path>,
+    svg: Option<&path::Path>,
+) -> io::Result<()> {
+    let progress = Progress::collect(year, runs);
+    let markdown = progress.to_markdown();
+    match update {
+        Some(path) => {
+            let document = fs::read_to_string(path)?;
+            let document = replace_region(&document, &markdown).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("expected {START_MARKER} and {END_MARKER} in {path:?}"),
+                )
+            })?;
+            fs::write(path, document)?;
+        }
+        None => print!("{markdown}"),
+    }
+    if let Some(path) = svg {
+        fs::write(path, progress.to_svg())?;
+    }
+    Ok(())
+}
+
+impl Progress {
+    // Solve every implemented puzzle a number of times to find the median runtime
+    pub fn collect(year: usize, runs: usize) -> Self {
+        let answers = Answers::load(year);
+        let days = (1..=25)
+            .filter(|day| {
+                Puzzle::new(year, *day, Part::One)
+                    .retrieve_solver()
+                    .is_some()
+            })
+            .map(|day| {
+                let mut verified = [None; 2];
+                let mut medians = [None; 2];
+                for (i, part) in [Part::One, Part::Two].into_iter().enumerate() {
+                    let puzzle = Puzzle::new(year, day, part);
+                    let _span = puzzle.span().entered();
+                    let mut durations = Vec::new();
+                    for _ in 0..runs.max(1) {
+                        let Ok(outcome) = puzzle.load_and_solve() else {
+                            break;
+                        };
+                        verified[i] = answers
+                            .get(day, part)
+                            .map(|answer| outcome.solution() == Some(answer));
+                        if let PuzzleOutcome::Solved { duration, .. } = outcome {
+                            durations.push(duration);
+                        }
+                    }
+                    medians[i] = median(durations);
+                }
+                DayProgress {
+                    day,
+                    stars: answers.stars(day),
+                    verified,
+                    medians,
+                }
+            })
+            .collect();
+        Self { year, days }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let stars = self.days.iter().map(|day| day.stars).sum::<usize>();
+        writeln!(markdown, "### {} ({stars}/50 ★)", self.year).unwrap();
+        writeln!(markdown).unwrap();
+        writeln!(markdown, "| Day | Stars | Part 1 | Part 2 |").unwrap();
+        writeln!(markdown, "|----:|:-----:|-------:|-------:|").unwrap();
+        for day in &self.days {
+            let cell = |i: usize| {
+                let verified = match day.verified[i] {
+                    Some(true) => " ✓",
+                    Some(false) => " ✗",
+                    None => "",
+                };
+                match day.medians[i] {
+                    Some(median) => format!("{}{verified}", duration_to_string(median).trim()),
+                    None => "–".to_string(),
+                }
+            };
+            writeln!(
+                markdown,
+                "| {:02} | {} | {} | {} |",
+                day.day,
+                "★".repeat(day.stars),
+                cell(0),
+                cell(1)
+            )
+            .unwrap();
+        }
+        markdown
+    }
+
+    // A 5 by 5 calendar with gold and silver squares for two and one stars
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="210" height="230" font-family="monospace" font-size="14">"#
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"  <text x="105" y="16" text-anchor="middle">{}</text>"#,
+            self.year
+        )
+        .unwrap();
+        for day in 1..=25 {
+            let stars = self
+                .days
+                .iter()
+                .find(|progress| progress.day == day)
+                .map_or(0, |progress| progress.stars);
+            let colour = match stars {
+                2 => "#ffd700",
+                1 => "#c0c0c0",
+                _ => "#444444",
+            };
+            let x = 5 + 40 * ((day - 1) % 5);
+            let y = 25 + 40 * ((day - 1) / 5);
+            writeln!(
+                svg,
+                r#"  <rect x="{x}" y="{y}" width="36" height="36" rx="4" fill="{colour}"/>"#
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                r#"  <text x="{}" y="{}" text-anchor="middle">{day}</text>"#,
+                x + 18,
+                y + 23
+            )
+            .unwrap();
+        }
+        writeln!(svg, "</svg>").unwrap();
+        svg
+    }
+}
+
+// The median of the runs, averaging the two middle ones for an even count
+fn median(mut durations: Vec<time::Duration>) -> Option<time::Duration> {
+    durations.sort();
+    let middle = durations.len() / 2;
+    match durations.len() {
+        0 => None,
+        len if len % 2 == 1 => Some(durations[middle]),
+        _ => Some((durations[middle - 1] + durations[middle]) / 2),
+    }
+}
+
+// Replace everything between the progress markers, keeping the markers
+fn replace_region(document: &str, content: &str) -> Option<String> {
+    let start = document.find(START_MARKER)? + START_MARKER.len();
+    let end = start + document[start..].find(END_MARKER)?;
+    Some(format!(
+        "{}\n{content}{}",
+        &document[..start],
+        &document[end..]
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn progress() -> Progress {
+        Progress {
+            year: 2023,
+            days: vec![
+                DayProgress {
+                    day: 1,
+                    stars: 2,
+                    verified: [Some(true), Some(false)],
+                    medians: [
+                        Some(time::Duration::from_micros(12)),
+                        Some(time::Duration::from_millis(34)),
+                    ],
+                },
+                DayProgress {
+                    day: 2,
+                    stars: 0,
+                    verified: [None, None],
+                    medians: [Some(time::Duration::from_micros(5)), None],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn markdown_table() {
+        let markdown = progress().to_markdown();
+        assert!(markdown.starts_with("### 2023 (2/50 ★)\n"));
+        assert!(markdown.contains("| 01 | ★★ | 12 µs ✓ | 34 ms ✗ |\n"));
+        assert!(markdown.contains("| 02 |  | 5 µs | – |\n"));
+    }
+
+    #[test]
+    fn svg_calendar() {
+        let svg = progress().to_svg();
+        assert_eq!(svg.matches("<rect").count(), 25);
+        assert_eq!(svg.matches("#ffd700").count(), 1);
+    }
+
+    #[test]
+    fn medians() {
+        let micros = |values: &[u64]| {
+            values
+                .iter()
+                .map(|&value| time::Duration::from_micros(value))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(median(Vec::new()), None);
+        assert_eq!(
+            median(micros(&[3, 1, 2])),
+            Some(time::Duration::from_micros(2))
+        );
+        assert_eq!(
+            median(micros(&[4, 1, 10, 2])),
+            Some(time::Duration::from_micros(3))
+        );
+    }
+
+    #[test]
+    fn replace_marked_region() {
+        let document = format!("# Title\n{START_MARKER}\nold\n{END_MARKER}\nrest\n");
+        assert_eq!(
+            replace_region(&document, "new\n").unwrap(),
+            format!("# Title\n{START_MARKER}\nnew\n{END_MARKER}\nrest\n")
+        );
+        assert_eq!(replace_region("no markers", "new\n"), None);
+    }
+}
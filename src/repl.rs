@@ -0,0 +1,194 @@
+use crate::solver::{Error, Part, Puzzle, Records};
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  1, 2              solve part 1 or part 2 with the current input
+  model <1|2>       parse the input the way part 1 or part 2 does
+  count             print the number of parsed records
+  show <n>          print record n in full
+  list [n]          print the first n records, or all of them
+  filter <text>     print the records containing the text
+  paste             replace the input with lines up to a line with a single '.'
+  reload            load the puzzle input from its file again
+  help              print this message
+  quit              leave the REPL";
+
+struct Repl {
+    year: usize,
+    day: usize,
+    part: Part,
+    input: String,
+    records: Result<Records, Error>,
+}
+
+pub fn run(year: usize, day: usize) -> io::Result<()> {
+    let puzzle = Puzzle::new(year, day, Part::One);
+    if puzzle.retrieve_model().is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{year}/{day:02} does not expose a parsed model"),
+        ));
+    }
+    let mut repl = Repl::new(year, day, puzzle.load_input()?);
+    println!("Exploring {year}/{day:02}, type 'help' for a list of commands");
+
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let mut command = String::new();
+        if stdin.read_line(&mut command)? == 0 {
+            return Ok(());
+        }
+        match repl.execute(command.trim(), &mut stdin)? {
+            Some(output) => println!("{output}"),
+            None => return Ok(()),
+        }
+    }
+}
+
+impl Repl {
+    fn new(year: usize, day: usize, input: String) -> Self {
+        let mut repl = Self {
+            year,
+            day,
+            part: Part::One,
+            input,
+            records: Ok(Vec::new()),
+        };
+        repl.parse();
+        repl
+    }
+
+    fn puzzle(&self, part: Part) -> Puzzle {
+        Puzzle::new(self.year, self.day, part)
+    }
+
+    fn parse(&mut self) {
+        self.records = match self.puzzle(self.part).retrieve_model() {
+            Some(model) => model(&self.input),
+            None => Err(Error::NoSolution),
+        };
+    }
+
+    // Run a single command, returning its output or `None` to quit. Pasted
+    // input is read from the given reader.
+    fn execute(&mut self, command: &str, reader: &mut impl BufRead) -> io::Result<Option<String>> {
+        let (command, argument) = command.split_once(' ').unwrap_or((command, ""));
+        let argument = argument.trim();
+        let output = match (command, argument) {
+            ("1", _) => self.solve(Part::One),
+            ("2", _) => self.solve(Part::Two),
+            ("model", "1") => self.set_part(Part::One),
+            ("model", "2") => self.set_part(Part::Two),
+            ("count", _) => self.with_records(|records| records.len().to_string()),
+            ("show", n) => match n.parse::<usize>() {
+                Ok(n) => self.with_records(|records| match records.get(n) {
+                    Some(record) => format!("{record:#?}"),
+                    None => format!("there are only {} records", records.len()),
+                }),
+                Err(_) => "usage: show <n>".to_string(),
+            },
+            ("list", n) => {
+                let n = n.parse().unwrap_or(usize::MAX);
+                self.with_records(|records| list(records.iter().enumerate().take(n)))
+            }
+            ("filter", text) => self.with_records(|records| {
+                list(
+                    records
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, record)| format!("{record:?}").contains(text)),
+                )
+            }),
+            ("paste", _) => {
+                let mut snippet = String::new();
+                for line in reader.lines() {
+                    let line = line?;
+                    if line == "." {
+                        break;
+                    }
+                    snippet.push_str(&line);
+                    snippet.push('\n');
+                }
+                self.input = self.puzzle(self.part).normalise(&snippet);
+                self.parse();
+                self.summary()
+            }
+            ("reload", _) => {
+                self.input = self.puzzle(self.part).load_input()?;
+                self.parse();
+                self.summary()
+            }
+            ("help", _) => HELP.to_string(),
+            ("quit" | "exit", _) => return Ok(None),
+            ("", _) => String::new(),
+            _ => format!("unknown command '{command}', type 'help' for a list of commands"),
+        };
+        Ok(Some(output))
+    }
+
+    fn solve(&self, part: Part) -> String {
+        let puzzle = self.puzzle(part);
+        format!("{puzzle}: {}", puzzle.solve(&self.input))
+    }
+
+    fn set_part(&mut self, part: Part) -> String {
+        self.part = part;
+        self.parse();
+        self.summary()
+    }
+
+    fn summary(&self) -> String {
+        self.with_records(|records| format!("parsed {} records", records.len()))
+    }
+
+    fn with_records(&self, f: impl FnOnce(&Records) -> String) -> String {
+        match &self.records {
+            Ok(records) => f(records),
+            Err(error) => format!("could not parse the input: {error}"),
+        }
+    }
+}
+
+fn list<'a>(records: impl Iterator<Item = (usize, &'a Box<dyn std::fmt::Debug>)>) -> String {
+    records
+        .map(|(i, record)| format!("{i:4}: {record:?}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn execute(repl: &mut Repl, command: &str) -> String {
+        repl.execute(command, &mut io::empty()).unwrap().unwrap()
+    }
+
+    #[test]
+    fn explore_records() {
+        let input = "32T3K 765\nT55J5 684\nKK677 28";
+        let mut repl = Repl::new(2023, 7, input.to_string());
+        assert_eq!(execute(&mut repl, "count"), "3");
+        assert!(execute(&mut repl, "show 2").contains("TwoPair"));
+        assert_eq!(execute(&mut repl, "show 3"), "there are only 3 records");
+        assert!(execute(&mut repl, "filter ThreeOfAKind").starts_with("   1: "));
+        assert_eq!(execute(&mut repl, "list 2").lines().count(), 2);
+        assert!(execute(&mut repl, "model 2").contains("3 records"));
+        assert!(execute(&mut repl, "filter FourOfAKind").starts_with("   1: "));
+        assert_eq!(repl.execute("quit", &mut io::empty()).unwrap(), None);
+    }
+
+    #[test]
+    fn paste_and_solve() {
+        let mut repl = Repl::new(2023, 6, String::new());
+        assert!(execute(&mut repl, "count").starts_with("could not parse"));
+        let mut pasted = "Time:      7  15   30\r\nDistance:  9  40  200\n.\nignored\n".as_bytes();
+        let output = repl.execute("paste", &mut pasted).unwrap().unwrap();
+        assert_eq!(output, "parsed 3 records");
+        assert!(execute(&mut repl, "1").starts_with("2023/06a:          288"));
+        assert!(execute(&mut repl, "2").starts_with("2023/06b:        71503"));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::duration_to_string;
+use crate::solver::PuzzleOutcome;
+use std::{
+    env,
+    fmt::{self, Write},
+    io::{self, IsTerminal},
+    time,
+};
+
+const BAR_WIDTH: usize = 10;
+
+// Characters to draw the table with, falling back to ASCII when the output is
+// not a terminal, e.g. when it is written to a file or piped into another tool
+struct Glyphs {
+    vertical: char,
+    horizontal: char,
+    cross: char,
+    full: char,
+    eighths: [char; 8],
+    slowest: char,
+}
+
+const UNICODE: Glyphs = Glyphs {
+    vertical: '│',
+    horizontal: '─',
+    cross: '┼',
+    full: '█',
+    eighths: [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'],
+    slowest: '◀',
+};
+
+const ASCII: Glyphs = Glyphs {
+    vertical: '|',
+    horizontal: '-',
+    cross: '+',
+    full: '#',
+    eighths: [' '; 8],
+    slowest: '<',
+};
+
+// ANSI styles, only emitted when colours are enabled
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+
+pub struct Report {
+    year: usize,
+    rows: Vec<(usize, [PuzzleOutcome; 2])>,
+    colour: bool,
+    glyphs: &'static Glyphs,
+}
+
+impl Report {
+    pub fn new(year: usize) -> Self {
+        Self {
+            year,
+            rows: Vec::new(),
+            colour: use_colour(),
+            glyphs: if io::stdout().is_terminal() {
+                &UNICODE
+            } else {
+                &ASCII
+            },
+        }
+    }
+
+    pub fn push(&mut self, day: usize, outcomes: [PuzzleOutcome; 2]) {
+        self.rows.push((day, outcomes));
+    }
+
+    pub fn total_duration(&self) -> time::Duration {
+        self.rows
+            .iter()
+            .flat_map(|(_, outcomes)| outcomes)
+            .map(measured)
+            .sum()
+    }
+
+    fn part_duration(&self, part: usize) -> time::Duration {
+        self.rows
+            .iter()
+            .map(|(_, outcomes)| measured(&outcomes[part]))
+            .sum()
+    }
+
+    fn slowest(&self) -> time::Duration {
+        self.rows
+            .iter()
+            .flat_map(|(_, outcomes)| outcomes)
+            .map(measured)
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn rule(&self) -> String {
+        let cell = |width: usize| self.glyphs.horizontal.to_string().repeat(width);
+        [6, 17, 22, 17, 22]
+            .map(cell)
+            .join(&self.glyphs.cross.to_string())
+    }
+
+    // Wrap the text in the given style if colours are enabled
+    fn paint(&self, style: &str, text: &str) -> String {
+        if self.colour {
+            format!("{style}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn answer_cell(&self, outcome: &PuzzleOutcome) -> String {
+        match outcome {
+            PuzzleOutcome::Solved { solution, .. } | PuzzleOutcome::Cached { solution, .. } => {
+                self.paint(GREEN, &format!("{solution:>15}"))
+            }
+            PuzzleOutcome::Errored { .. } => self.paint(RED, &format!("{:>15}", "error")),
+            PuzzleOutcome::Failed => self.paint(DIM, &format!("{:>15}", "N/A")),
+        }
+    }
+
+    fn time_cells(&self, outcome: &PuzzleOutcome, slowest: time::Duration) -> String {
+        let duration = outcome.duration();
+        // The slowest puzzle gets a marker as well, so it still stands out
+        // without colours
+        let is_slowest = duration == slowest && !slowest.is_zero();
+        let text = format!(
+            "{} {} {}",
+            duration_to_string(duration),
+            bar(duration, slowest, self.glyphs),
+            if is_slowest { self.glyphs.slowest } else { ' ' }
+        );
+        match outcome {
+            PuzzleOutcome::Failed => self.paint(DIM, &format!("{:>7} {:BAR_WIDTH$}  ", "", "")),
+            // Cached puzzles show the time they took when they were last solved
+            PuzzleOutcome::Cached { .. } => self.paint(
+                DIM,
+                &format!("{} {:BAR_WIDTH$}  ", duration_to_string(duration), "cached"),
+            ),
+            _ if is_slowest => self.paint(&format!("{BOLD}{YELLOW}"), &text),
+            _ => text,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.glyphs.vertical;
+        let header = format!(
+            "{:>5} {v} {:>15} {v} {:>7} {:BAR_WIDTH$}   {v} {:>15} {v} {:>7} {:BAR_WIDTH$}",
+            self.year, "Part 1", "Time", "", "Part 2", "Time", ""
+        );
+        writeln!(f, "{}", self.paint(BOLD, &header))?;
+        writeln!(f, "{}", self.rule())?;
+
+        // One row per day with both parts side by side, with bars relative to
+        // the slowest puzzle in the table
+        let slowest = self.slowest();
+        for (day, [one, two]) in &self.rows {
+            writeln!(
+                f,
+                "{day:>5} {v} {} {v} {} {v} {} {v} {}",
+                self.answer_cell(one),
+                self.time_cells(one, slowest),
+                self.answer_cell(two),
+                self.time_cells(two, slowest)
+            )?;
+        }
+
+        // Totals per part and for the whole year
+        writeln!(f, "{}", self.rule())?;
+        let totals = format!(
+            "{:>5} {v} {:>15} {v} {} {:BAR_WIDTH$}   {v} {:>15} {v} {} {:BAR_WIDTH$}",
+            "Total",
+            "",
+            duration_to_string(self.part_duration(0)),
+            "",
+            "",
+            duration_to_string(self.part_duration(1)),
+            ""
+        );
+        writeln!(f, "{}", self.paint(BOLD, &totals))?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Total computation time: {}",
+            duration_to_string(self.total_duration())
+        )?;
+
+        // List the errors below the table, as they do not fit in a cell
+        let mut errors = String::new();
+        for (day, outcomes) in &self.rows {
+            for (outcome, part) in outcomes.iter().zip(["a", "b"]) {
+                if let PuzzleOutcome::Errored { error, .. } = outcome {
+                    writeln!(errors, "{}/{day:02}{part}: {error}", self.year)?;
+                }
+            }
+        }
+        if !errors.is_empty() {
+            writeln!(f)?;
+            write!(f, "{}", self.paint(RED, &errors))?;
+        }
+        Ok(())
+    }
+}
+
+// Cached answers took no time in this run, so they are left out of the totals
+// and of the scale of the bars
+fn measured(outcome: &PuzzleOutcome) -> time::Duration {
+    match outcome {
+        PuzzleOutcome::Cached { .. } => time::Duration::ZERO,
+        _ => outcome.duration(),
+    }
+}
+
+// Draw a bar with a length relative to the slowest puzzle, in eighths of a cell
+fn bar(duration: time::Duration, slowest: time::Duration, glyphs: &Glyphs) -> String {
+    let eighths = match slowest.as_nanos() {
+        0 => 0,
+        slowest => (duration.as_nanos() * (8 * BAR_WIDTH) as u128 / slowest) as usize,
+    };
+    let mut bar = glyphs.full.to_string().repeat(eighths / 8);
+    if eighths % 8 > 0 {
+        bar.push(glyphs.eighths[eighths % 8]);
+    }
+    format!("{bar:BAR_WIDTH$}")
+}
+
+// Only use colours when writing to a terminal and `NO_COLOR` is not set
+fn use_colour() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solver::Error;
+
+    fn report(colour: bool) -> Report {
+        let solved = |micros| PuzzleOutcome::Solved {
+            solution: 42,
+            duration: time::Duration::from_micros(micros),
+        };
+        let errored = PuzzleOutcome::Errored {
+            error: Error::Overflow,
+            duration: time::Duration::from_micros(3),
+        };
+        let mut report = Report {
+            year: 2023,
+            rows: Vec::new(),
+            colour,
+            glyphs: &UNICODE,
+        };
+        report.push(1, [solved(100), solved(300)]);
+        report.push(2, [errored, PuzzleOutcome::Failed]);
+        report
+    }
+
+    #[test]
+    fn plain_output() {
+        let output = report(false).to_string();
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("    1 │              42 │  100 µs ███▎         │"));
+        assert!(output.contains(&format!(" 300 µs {} ◀\n", bar_full())));
+        assert_eq!(output.matches('◀').count(), 1);
+        assert!(output.contains("Total computation time:  403 µs"));
+        assert!(output.contains("2023/02a: arithmetic overflow"));
+    }
+
+    #[test]
+    fn cached_output() {
+        let mut report = report(false);
+        let cached = PuzzleOutcome::Cached {
+            solution: 7,
+            duration: time::Duration::from_millis(5),
+        };
+        report.push(3, [cached, PuzzleOutcome::Failed]);
+        let output = report.to_string();
+        assert!(output.contains("    3 │               7 │ 5000 µs cached       │"));
+        assert!(output.contains("    1 │              42 │  100 µs ███▎         │"));
+        assert!(output.contains("Total computation time:  403 µs"));
+    }
+
+    #[test]
+    fn ascii_output() {
+        let output = Report {
+            glyphs: &ASCII,
+            ..report(false)
+        }
+        .to_string();
+        assert!(!output.contains(['│', '─', '┼', '█']));
+        assert!(output.contains("    1 |              42 |  100 µs ###          |"));
+        assert!(output.contains(" 300 µs ########## <\n"));
+        assert!(output.contains("------+-----------------+"));
+    }
+
+    #[test]
+    fn coloured_output() {
+        let output = report(true).to_string();
+        assert!(output.contains(&format!("{BOLD}{YELLOW} 300 µs {} ◀{RESET}", bar_full())));
+        assert!(output.contains(&format!("{RED}          error{RESET}")));
+    }
+
+    fn bar_full() -> String {
+        "█".repeat(BAR_WIDTH)
+    }
+
+    #[test]
+    fn bar_lengths() {
+        let slowest = time::Duration::from_millis(8);
+        let millis = time::Duration::from_millis(1);
+        assert_eq!(bar(time::Duration::ZERO, slowest, &UNICODE).trim_end(), "");
+        assert_eq!(bar(millis, slowest, &UNICODE).trim_end(), "█▎");
+        assert_eq!(bar(millis, slowest, &ASCII).trim_end(), "#");
+        assert_eq!(bar(slowest, slowest, &UNICODE), bar_full());
+        assert_eq!(bar(slowest, time::Duration::ZERO, &UNICODE).trim_end(), "");
+    }
+}
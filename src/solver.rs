@@ -1,24 +1,62 @@
+#[cfg(test)]
+use crate::check;
 use crate::duration_to_string;
+use crate::util::memo;
 use crate::year2023;
-use std::{fmt, fs, io, path, time};
+use std::{fmt, fs, io, panic, path, sync::mpsc, thread, time};
+use tracing::{debug, info_span, Span};
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Part {
     One,
     Two,
 }
 
+pub type Solver = fn(&str) -> Result<usize, Error>;
+
+// Parsed records of a puzzle input, for exploring them in the REPL
+pub type Records = Vec<Box<dyn fmt::Debug>>;
+pub type Model = fn(&str) -> Result<Records, Error>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    Parse(String),
+    Overflow,
+    NoSolution,
+    Timeout,
+    Panicked,
+}
+
+// How trailing whitespace is treated when loading an input
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Whitespace {
+    TrimEnd,
+    TrimLines,
+}
+
 pub struct Puzzle {
     year: usize,
     day: usize,
     part: Part,
+    timeout: Option<time::Duration>,
 }
 
+#[derive(Default)]
 pub enum PuzzleOutcome {
     Solved {
         solution: usize,
         duration: time::Duration,
     },
+    Errored {
+        error: Error,
+        duration: time::Duration,
+    },
+    // Solved in an earlier run with the same input and build
+    Cached {
+        solution: usize,
+        duration: time::Duration,
+    },
+    #[default]
     Failed,
 }
 
@@ -29,9 +67,28 @@ impl PuzzleOutcome {
                 solution: _,
                 duration,
             } => *duration,
+            Self::Errored { error: _, duration } => *duration,
+            Self::Cached {
+                solution: _,
+                duration,
+            } => *duration,
             Self::Failed => time::Duration::default(),
         }
     }
+
+    pub fn solution(&self) -> Option<usize> {
+        match self {
+            Self::Solved {
+                solution,
+                duration: _,
+            }
+            | Self::Cached {
+                solution,
+                duration: _,
+            } => Some(*solution),
+            Self::Errored { .. } | Self::Failed => None,
+        }
+    }
 }
 
 impl fmt::Display for PuzzleOutcome {
@@ -40,11 +97,28 @@ impl fmt::Display for PuzzleOutcome {
             Self::Solved { solution, duration } => {
                 write!(f, "{:12} ({})", solution, duration_to_string(*duration))
             }
+            Self::Errored { error, duration: _ } => write!(f, "error: {error}"),
+            Self::Cached {
+                solution,
+                duration: _,
+            } => write!(f, "{solution:12} (cached)"),
             Self::Failed => write!(f, "N/A"),
         }
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(message) => write!(f, "invalid input ({message})"),
+            Self::Overflow => write!(f, "arithmetic overflow"),
+            Self::NoSolution => write!(f, "no solution"),
+            Self::Timeout => write!(f, "timed out"),
+            Self::Panicked => write!(f, "solver panicked"),
+        }
+    }
+}
+
 impl fmt::Display for Puzzle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let part_str: &str = match self.part {
@@ -55,31 +129,57 @@ impl fmt::Display for Puzzle {
     }
 }
 
-impl Default for PuzzleOutcome {
-    fn default() -> Self {
-        Self::Failed
-    }
-}
-
 impl Puzzle {
     pub fn new(year: usize, day: usize, part: Part) -> Self {
-        Self { year, day, part }
+        Self {
+            year,
+            day,
+            part,
+            timeout: None,
+        }
+    }
+
+    // Give up on solving after the timeout, leaving the solver running in the
+    // background, so only use this when the process exits soon after
+    pub fn with_timeout(mut self, timeout: Option<time::Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // The span to enter around everything done for this puzzle, whether it is
+    // solved, taken from the cache or profiled
+    pub fn span(&self) -> Span {
+        info_span!("puzzle", year = self.year, day = self.day, part = ?self.part)
     }
 
     pub fn load_and_solve(&self) -> Result<PuzzleOutcome, io::Error> {
-        // Try to load input
-        let input = self.load_input()?;
+        self.load_and_solve_from(&self.input_path())
+    }
 
-        // Trim trailing whitespace for easy splitting into lines
-        let input = input.trim_end();
+    pub fn load_and_solve_from(&self, path: &path::Path) -> Result<PuzzleOutcome, io::Error> {
+        // Try to load input
+        let input = self.load_input_from(path)?;
 
         // Actually solve the puzzle
-        Ok(self.solve(input))
+        Ok(self.solve(&input))
+    }
+
+    pub fn load_input(&self) -> Result<String, io::Error> {
+        self.load_input_from(&self.input_path())
     }
 
-    fn load_input(&self) -> Result<std::string::String, io::Error> {
+    fn load_input_from(&self, path: &path::Path) -> Result<String, io::Error> {
+        let _span = info_span!("load", path = %path.display()).entered();
+        let input = fs::read_to_string(path)?;
+        debug!(bytes = input.len(), "read input");
+
+        // Clean up the input so solvers can rely on `\n`-separated lines
+        Ok(normalise(&input, self.retrieve_whitespace()))
+    }
+
+    pub fn input_path(&self) -> path::PathBuf {
         // Set the path for the given day
-        let path_buf: path::PathBuf = [
+        [
             ".",
             "input",
             "data",
@@ -87,27 +187,39 @@ impl Puzzle {
             format!("day{:02}.txt", self.day).as_str(),
         ]
         .iter()
-        .collect();
-
-        // Try to read the input
-        let input = fs::read_to_string(&path_buf)?;
-        Ok(input)
+        .collect()
     }
 
-    fn solve(&self, input: &str) -> PuzzleOutcome {
+    pub fn solve(&self, input: &str) -> PuzzleOutcome {
         match self.retrieve_solver() {
             Some(solver) => {
-                // Solve the puzzle and keep track of the time spent doing so
+                let _span = match self.part {
+                    Part::One => info_span!("part1"),
+                    Part::Two => info_span!("part2"),
+                }
+                .entered();
+
+                // Start without results cached by an earlier solve
+                memo::reset();
+
+                // Solve the puzzle and keep track of the time spent doing so, reporting
+                // a panicking solver like any other error
                 let start_instant = time::Instant::now();
-                let solution = solver(input);
+                let solution = match self.timeout {
+                    Some(timeout) => solve_within(solver, input, timeout),
+                    None => panic::catch_unwind(|| solver(input)).unwrap_or(Err(Error::Panicked)),
+                };
                 let duration = start_instant.elapsed();
-                PuzzleOutcome::Solved { solution, duration }
+                match solution {
+                    Ok(solution) => PuzzleOutcome::Solved { solution, duration },
+                    Err(error) => PuzzleOutcome::Errored { error, duration },
+                }
             }
             None => PuzzleOutcome::Failed,
         }
     }
 
-    fn retrieve_solver(&self) -> Option<fn(&str) -> usize> {
+    pub fn retrieve_solver(&self) -> Option<Solver> {
         match (self.year, self.day, self.part) {
             (2023, 1, Part::One) => Some(year2023::day01::part1),
             (2023, 1, Part::Two) => Some(year2023::day01::part2),
@@ -126,4 +238,150 @@ impl Puzzle {
             _ => None,
         }
     }
+
+    pub fn retrieve_model(&self) -> Option<Model> {
+        match (self.year, self.day, self.part) {
+            (2023, 1, Part::One) => Some(year2023::day01::model1),
+            (2023, 1, Part::Two) => Some(year2023::day01::model2),
+            (2023, 2, _) => Some(year2023::day02::model),
+            (2023, 3, _) => Some(year2023::day03::model),
+            (2023, 4, _) => Some(year2023::day04::model),
+            (2023, 5, _) => Some(year2023::day05::model),
+            (2023, 6, Part::One) => Some(year2023::day06::model1),
+            (2023, 6, Part::Two) => Some(year2023::day06::model2),
+            (2023, 7, Part::One) => Some(year2023::day07::model1),
+            (2023, 7, Part::Two) => Some(year2023::day07::model2),
+            _ => None,
+        }
+    }
+
+    // Clean up an input that was not loaded from a file, e.g. a pasted snippet
+    pub fn normalise(&self, input: &str) -> String {
+        normalise(input, self.retrieve_whitespace())
+    }
+
+    fn retrieve_whitespace(&self) -> Whitespace {
+        match (self.year, self.day) {
+            (2023, 3) => Whitespace::TrimLines,
+            _ => Whitespace::TrimEnd,
+        }
+    }
+
+    // Slow but obviously correct solvers to test the optimised ones against
+    #[cfg(test)]
+    pub fn retrieve_reference(&self) -> Option<Solver> {
+        match (self.year, self.day, self.part) {
+            (2023, 5, Part::Two) => Some(year2023::day05::part2_brute_force),
+            (2023, 6, Part::One) => Some(year2023::day06::part1_brute_force),
+            (2023, 6, Part::Two) => Some(year2023::day06::part2_brute_force),
+            _ => None,
+        }
+    }
+
+    // Random input generators for days that have a reference solver
+    #[cfg(test)]
+    pub fn retrieve_generator(&self) -> Option<check::Generator> {
+        match (self.year, self.day) {
+            (2023, 5) => Some(year2023::day05::generate),
+            (2023, 6) => Some(year2023::day06::generate),
+            _ => None,
+        }
+    }
+}
+
+// Strip a byte order mark, convert all line endings to `\n` and trim trailing
+// whitespace according to the given policy
+pub fn normalise(input: &str, whitespace: Whitespace) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+    match whitespace {
+        Whitespace::TrimEnd => input.trim_end().to_string(),
+        Whitespace::TrimLines => input
+            .trim_end()
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+// Box the parsed records of a puzzle, so they can be printed in the REPL
+pub fn records<T, I>(records: I) -> Records
+where
+    T: fmt::Debug + 'static,
+    I: IntoIterator<Item = T>,
+{
+    records
+        .into_iter()
+        .map(|record| Box::new(record) as Box<dyn fmt::Debug>)
+        .collect()
+}
+
+// Run the solver on a worker thread and give up after `timeout`. There is no
+// way to stop the thread, so an abandoned solver keeps using a CPU until it
+// finishes or the process exits.
+fn solve_within(solver: Solver, input: &str, timeout: time::Duration) -> Result<usize, Error> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    let span = Span::current();
+    thread::spawn(move || {
+        let _span = span.entered();
+        // The receiver is gone after a timeout, which is fine
+        let _ = sender.send(solver(&input));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(solution) => solution,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Error::Timeout),
+        // The sender is dropped without sending when the solver panics
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(Error::Panicked),
+    }
+}
+
+// Sum the values while guarding against overflow on adversarial inputs
+pub fn checked_sum<I>(values: I) -> Result<usize, Error>
+where
+    I: IntoIterator<Item = Result<usize, Error>>,
+{
+    values.into_iter().try_fold(0_usize, |total, value| {
+        total.checked_add(value?).ok_or(Error::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalise_line_endings() {
+        let input = "\u{feff}a b\r\nc\rd\n\n";
+        assert_eq!(normalise(input, Whitespace::TrimEnd), "a b\nc\nd");
+    }
+
+    #[test]
+    fn normalise_trailing_whitespace() {
+        let input = " a \t\r\n\r\n b  \r\n  ";
+        assert_eq!(normalise(input, Whitespace::TrimEnd), " a \t\n\n b");
+        assert_eq!(normalise(input, Whitespace::TrimLines), " a\n\n b");
+    }
+
+    #[test]
+    fn abandon_slow_solvers() {
+        fn slow(input: &str) -> Result<usize, Error> {
+            thread::sleep(time::Duration::from_secs(10));
+            Ok(input.len())
+        }
+        let timeout = time::Duration::from_millis(10);
+        assert_eq!(solve_within(slow, "", timeout), Err(Error::Timeout));
+        let timeout = time::Duration::from_secs(10);
+        assert_eq!(solve_within(|input| Ok(input.len()), "abc", timeout), Ok(3));
+    }
+
+    #[test]
+    fn report_panicking_solvers() {
+        fn panicking(_: &str) -> Result<usize, Error> {
+            panic!("solver bug")
+        }
+        let timeout = time::Duration::from_secs(10);
+        assert_eq!(solve_within(panicking, "", timeout), Err(Error::Panicked));
+    }
 }
@@ -0,0 +1,317 @@
+use crate::answers::{Answers, Timings};
+use crate::duration_to_string;
+use crate::examples::EXAMPLES;
+use crate::solver::{Part, Puzzle, PuzzleOutcome};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute, queue,
+    style::{Print, Stylize},
+    terminal,
+};
+use std::{
+    io::{self, Write},
+    panic,
+    sync::{mpsc, Arc},
+    thread, time,
+};
+
+const FIRST_YEAR: usize = 2015;
+const DAYS: usize = 25;
+const COLUMNS: usize = 5;
+const LOG_LINES: usize = 12;
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(100);
+
+// Messages sent from the thread running a puzzle back to the dashboard
+enum Message {
+    Line(String),
+    Timing(usize, usize, Part, time::Duration),
+    Done,
+}
+
+struct App {
+    year: usize,
+    day: usize,
+    answers: Answers,
+    timings: Timings,
+    log: Vec<String>,
+    running: Option<mpsc::Receiver<Message>>,
+    quit: bool,
+}
+
+pub fn run(year: usize) -> io::Result<()> {
+    let mut stdout = io::stdout();
+
+    // Make sure the terminal is restored if the dashboard panics. Panics of
+    // solvers are caught and shown in the log, so printing them as well would
+    // only draw over the dashboard
+    let ui_thread = thread::current().id();
+    let previous_hook = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&previous_hook);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().id() == ui_thread {
+            restore_terminal(&mut io::stdout()).ok();
+            hook(info);
+        }
+    }));
+
+    let result = terminal::enable_raw_mode()
+        .and_then(|()| execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide))
+        .and_then(|()| App::new(year).event_loop(&mut stdout));
+    let restored = restore_terminal(&mut stdout);
+    panic::set_hook(Box::new(move |info| previous_hook(info)));
+    result.and(restored)
+}
+
+fn restore_terminal(stdout: &mut io::Stdout) -> io::Result<()> {
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()
+}
+
+impl App {
+    fn new(year: usize) -> Self {
+        Self {
+            year,
+            day: 1,
+            answers: Answers::load(year),
+            timings: Timings::load(year),
+            log: Vec::new(),
+            running: None,
+            quit: false,
+        }
+    }
+
+    fn event_loop(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+        while !self.quit {
+            self.receive();
+            self.draw(stdout)?;
+            if event::poll(POLL_INTERVAL)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_key(key.code);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Left => self.select(-1),
+            KeyCode::Right => self.select(1),
+            KeyCode::Up => self.select(-(COLUMNS as isize)),
+            KeyCode::Down => self.select(COLUMNS as isize),
+            KeyCode::Char('[') if self.year > FIRST_YEAR => self.set_year(self.year - 1),
+            KeyCode::Char(']') => self.set_year(self.year + 1),
+            KeyCode::Char('1') => self.start(Some(Part::One)),
+            KeyCode::Char('2') => self.start(Some(Part::Two)),
+            KeyCode::Char('e') => self.start(None),
+            KeyCode::Char('c') => self.log.clear(),
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ => (),
+        }
+    }
+
+    // Move the selection through the calendar, wrapping around at the ends
+    fn select(&mut self, offset: isize) {
+        let index = (self.day as isize - 1 + offset).rem_euclid(DAYS as isize);
+        self.day = index as usize + 1;
+    }
+
+    fn set_year(&mut self, year: usize) {
+        self.year = year;
+        self.answers = Answers::load(year);
+        self.timings = Timings::load(year);
+    }
+
+    // Run a part, or all examples if no part is given, on a separate thread
+    fn start(&mut self, part: Option<Part>) {
+        if self.running.is_some() {
+            return;
+        }
+        let (year, day) = (self.year, self.day);
+        let (sender, receiver) = mpsc::channel();
+        let answers = [Part::One, Part::Two].map(|part| self.answers.get(day, part));
+        thread::spawn(move || {
+            match part {
+                Some(part) => run_part(year, day, part, answers, &sender),
+                None => run_examples(year, day, &sender),
+            }
+            sender.send(Message::Done).ok();
+        });
+        self.running = Some(receiver);
+    }
+
+    fn receive(&mut self) {
+        let Some(receiver) = &self.running else {
+            return;
+        };
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                Message::Line(line) => self.log.push(line),
+                Message::Timing(year, day, part, duration) => {
+                    // The year may have been switched while the puzzle was running
+                    let saved = if year == self.year {
+                        self.timings.insert(day, part, duration)
+                    } else {
+                        Timings::load(year).insert(day, part, duration)
+                    };
+                    if let Err(error) = saved {
+                        self.log.push(format!("could not save timings ({error})"));
+                    }
+                }
+                Message::Done => {
+                    self.running = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    fn draw(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+        queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        let stars = (1..=DAYS).map(|day| self.answers.stars(day)).sum::<usize>();
+        let title = format!(" Advent of Code {}", self.year).bold();
+        queue!(stdout, cursor::MoveTo(0, 0), Print(title))?;
+        queue!(stdout, Print(format!("   {stars}/{} ★", 2 * DAYS).yellow()))?;
+
+        // The calendar itself
+        for day in 1..=DAYS {
+            let row = (day - 1) / COLUMNS;
+            let column = (day - 1) % COLUMNS;
+            let cell = format!(" {day:2} {} ", star_string(self.answers.stars(day)));
+            let implemented = Puzzle::new(self.year, day, Part::One)
+                .retrieve_solver()
+                .is_some();
+            let cell = match (day == self.day, implemented) {
+                (true, _) => cell.reverse(),
+                (false, true) => cell.yellow(),
+                (false, false) => cell.dark_grey(),
+            };
+            queue!(
+                stdout,
+                cursor::MoveTo(1 + 9 * column as u16, 2 + row as u16),
+                Print(cell)
+            )?;
+        }
+
+        // Details of the selected day
+        let timing = |part| {
+            self.timings
+                .get(self.day, part)
+                .map_or("-".to_string(), duration_to_string)
+        };
+        let details = format!(
+            " Day {:02}   part 1: {}   part 2: {}",
+            self.day,
+            timing(Part::One),
+            timing(Part::Two)
+        );
+        queue!(stdout, cursor::MoveTo(0, 8), Print(details.bold()))?;
+        if self.running.is_some() {
+            queue!(stdout, Print("   running…".cyan()))?;
+        }
+
+        // The most recent output
+        let start = self.log.len().saturating_sub(LOG_LINES);
+        for (i, line) in self.log[start..].iter().enumerate() {
+            queue!(stdout, cursor::MoveTo(1, 10 + i as u16), Print(line))?;
+        }
+
+        let help =
+            " ←↑↓→ select day   1/2 run part   e run examples   [/] change year   c clear   q quit";
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 11 + LOG_LINES as u16),
+            Print(help.dark_grey())
+        )?;
+        stdout.flush()
+    }
+}
+
+fn run_part(
+    year: usize,
+    day: usize,
+    part: Part,
+    answers: [Option<usize>; 2],
+    sender: &mpsc::Sender<Message>,
+) {
+    let puzzle = Puzzle::new(year, day, part);
+    let line = match puzzle.load_and_solve() {
+        Ok(outcome) => {
+            // Only solutions have a timing worth remembering
+            if let PuzzleOutcome::Solved { duration, .. } = outcome {
+                sender.send(Message::Timing(year, day, part, duration)).ok();
+            }
+            let answer = answers[part as usize];
+            format!("{puzzle}: {outcome}{}", verdict(&outcome, answer))
+        }
+        Err(error) => format!("{puzzle}: could not load input ({error})"),
+    };
+    sender.send(Message::Line(line)).ok();
+}
+
+fn run_examples(year: usize, day: usize, sender: &mpsc::Sender<Message>) {
+    let examples = EXAMPLES
+        .iter()
+        .filter(|example| example.year == year && example.day == day)
+        .collect::<Vec<_>>();
+    if examples.is_empty() {
+        let line = format!("{year}/{day:02}: no examples");
+        sender.send(Message::Line(line)).ok();
+    }
+    for example in examples {
+        let puzzle = example.puzzle();
+        let line = match puzzle.load_and_solve_from(&example.path()) {
+            Ok(outcome) => format!(
+                "{puzzle} {}: {outcome}{}",
+                example.file,
+                verdict(&outcome, Some(example.answer))
+            ),
+            Err(error) => format!("{puzzle} {}: {error}", example.file),
+        };
+        sender.send(Message::Line(line)).ok();
+    }
+}
+
+// Compare an outcome with the known answer, if there is one
+fn verdict(outcome: &PuzzleOutcome, answer: Option<usize>) -> String {
+    match (outcome.solution(), answer) {
+        (Some(solution), Some(answer)) if solution == answer => "  ✓".to_string(),
+        (Some(_), Some(answer)) => format!("  ✗ expected {answer}"),
+        _ => String::new(),
+    }
+}
+
+fn star_string(stars: usize) -> String {
+    (0..2).map(|i| if i < stars { '★' } else { '☆' }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn select_wraps_around() {
+        let mut app = App::new(2023);
+        app.select(-1);
+        assert_eq!(app.day, 25);
+        app.select(COLUMNS as isize);
+        assert_eq!(app.day, 5);
+        app.select(-(COLUMNS as isize));
+        assert_eq!(app.day, 25);
+    }
+
+    #[test]
+    fn verdicts() {
+        let solved = PuzzleOutcome::Solved {
+            solution: 42,
+            duration: time::Duration::ZERO,
+        };
+        assert_eq!(verdict(&solved, Some(42)), "  ✓");
+        assert_eq!(verdict(&solved, Some(41)), "  ✗ expected 41");
+        assert_eq!(verdict(&solved, None), "");
+        assert_eq!(verdict(&PuzzleOutcome::Failed, Some(42)), "");
+    }
+}
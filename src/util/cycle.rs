@@ -0,0 +1,157 @@
+use std::{collections::HashMap, hash::Hash};
+use tracing::debug;
+
+// The states of a simulation up to the first repeat, which starts a cycle of
+// `length` states after a prefix of `prefix` states
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct Cycle<S> {
+    pub prefix: usize,
+    pub length: usize,
+    states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    // The state after any number of steps, however large
+    #[allow(dead_code)]
+    pub fn state_after(&self, steps: usize) -> &S {
+        let index = if steps < self.prefix {
+            steps
+        } else {
+            self.prefix + (steps - self.prefix) % self.length
+        };
+        &self.states[index]
+    }
+}
+
+// Step through the states, remembering each of them, until one repeats. The
+// step function must eventually repeat a state for this to return.
+#[allow(dead_code)]
+pub fn find_cycle<S>(start: S, step: impl FnMut(&S) -> S) -> Cycle<S>
+where
+    S: Clone + Eq + Hash,
+{
+    match simulate(start, None, step) {
+        Ok(cycle) => cycle,
+        Err(_) => unreachable!("there is no limit on the number of steps"),
+    }
+}
+
+// The state after the number of steps, skipping whole cycles once a state
+// repeats and stopping early if the steps run out before that
+#[allow(dead_code)]
+pub fn state_after<S>(start: S, steps: usize, step: impl FnMut(&S) -> S) -> S
+where
+    S: Clone + Eq + Hash,
+{
+    match simulate(start, Some(steps), step) {
+        Ok(cycle) => cycle.state_after(steps).clone(),
+        Err(state) => state,
+    }
+}
+
+// Find the cycle, or return the state after the limit if that comes first
+fn simulate<S>(start: S, limit: Option<usize>, mut step: impl FnMut(&S) -> S) -> Result<Cycle<S>, S>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            let cycle = Cycle {
+                prefix,
+                length: states.len() - prefix,
+                states,
+            };
+            debug!(cycle.prefix, cycle.length, "found cycle");
+            return Ok(cycle);
+        }
+        if limit == Some(states.len()) {
+            return Err(state);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+// Brent's algorithm, which finds the prefix and cycle lengths without storing
+// or hashing states, at the cost of stepping through them a few more times
+#[allow(dead_code)]
+pub fn brent<S>(start: S, mut step: impl FnMut(&S) -> S) -> (usize, usize)
+where
+    S: Clone + Eq,
+{
+    // Find the cycle length by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare a cycle ahead, both meet at the start of the cycle
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    (prefix, length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 5 -> 6 -> 7 -> 0 and 3 -> 0, 4 -> 7, 8 -> 5, 9 -> 2
+    fn step(n: &u64) -> u64 {
+        (n * n + 1) % 10
+    }
+
+    #[test]
+    fn cycle_lengths() {
+        for (start, lengths) in [(0, (0, 6)), (3, (1, 6)), (5, (0, 6))] {
+            let cycle = find_cycle(start, step);
+            assert_eq!((cycle.prefix, cycle.length), lengths);
+            assert_eq!(brent(start, step), lengths);
+        }
+        assert_eq!(brent(0, |_| 4), (1, 1));
+        assert_eq!(find_cycle(0, |_| 4).length, 1);
+    }
+
+    #[test]
+    fn states_after_many_steps() {
+        let mut expected = 3;
+        for steps in 0..100 {
+            assert_eq!(state_after(3, steps, step), expected);
+            expected = step(&expected);
+        }
+        let cycle = find_cycle(3, step);
+        assert_eq!(
+            cycle.state_after(1_000_000_000),
+            cycle.state_after(1 + 999_999_999 % 6)
+        );
+    }
+
+    #[test]
+    fn stop_before_repeating() {
+        // counting up never repeats, but three steps are enough
+        assert_eq!(state_after(0, 3, |n| n + 1), 3);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::util::point::Point2;
+
+// The four directions on a grid, clockwise starting at the top
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(dead_code)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+// The eight compass directions, clockwise starting at the top
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir4 {
+    #[allow(dead_code)]
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    #[allow(dead_code)]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[allow(dead_code)]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[allow(dead_code)]
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    // The step to take in this direction, with y pointing down
+    #[allow(dead_code)]
+    pub fn offset(self) -> Point2 {
+        match self {
+            Self::Up => Point2::new(0, -1),
+            Self::Right => Point2::new(1, 0),
+            Self::Down => Point2::new(0, 1),
+            Self::Left => Point2::new(-1, 0),
+        }
+    }
+
+    // Parse the arrows and letters puzzles commonly use for directions
+    #[allow(dead_code)]
+    pub fn from_char(char: char) -> Option<Self> {
+        match char {
+            '^' | 'U' | 'N' => Some(Self::Up),
+            '>' | 'R' | 'E' => Some(Self::Right),
+            'v' | 'D' | 'S' => Some(Self::Down),
+            '<' | 'L' | 'W' => Some(Self::Left),
+            _ => None,
+        }
+    }
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    // Rotate by an eighth of a turn
+    #[allow(dead_code)]
+    pub fn rotate_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    #[allow(dead_code)]
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn offset(self) -> Point2 {
+        match self {
+            Self::N => Point2::new(0, -1),
+            Self::NE => Point2::new(1, -1),
+            Self::E => Point2::new(1, 0),
+            Self::SE => Point2::new(1, 1),
+            Self::S => Point2::new(0, 1),
+            Self::SW => Point2::new(-1, 1),
+            Self::W => Point2::new(-1, 0),
+            Self::NW => Point2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[2 * dir as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.turn_right(), Dir4::Up);
+        assert_eq!(Dir4::Right.reverse(), Dir4::Left);
+        assert_eq!(Dir8::N.rotate_left(), Dir8::NW);
+        assert_eq!(Dir8::NW.rotate_right(), Dir8::N);
+        assert_eq!(Dir8::SE.reverse(), Dir8::NW);
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::S);
+    }
+
+    #[test]
+    fn offsets_agree_with_rotations() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().offset(), dir.offset().rotate_right());
+            assert_eq!(Dir8::from(dir).offset(), dir.offset());
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+        for dir in Dir8::ALL {
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+    }
+}
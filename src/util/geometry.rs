@@ -0,0 +1,443 @@
+use crate::solver::Error;
+use crate::util::{math, point::Point2};
+use std::cmp::Ordering;
+
+// An exact fraction in lowest terms with a positive denominator
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(dead_code)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum Intersection {
+    None,
+    Point(Rational, Rational),
+    // The lines are the same, or the segments overlap in more than a point
+    Collinear,
+}
+
+impl Rational {
+    // Only built from intersections, whose denominators are positive
+    fn new(num: i128, den: i128) -> Self {
+        debug_assert!(den > 0);
+        let gcd = gcd_u128(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        Self {
+            num: num / gcd,
+            den: den / gcd,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn num(self) -> i128 {
+        self.num
+    }
+
+    #[allow(dead_code)]
+    pub fn den(self) -> i128 {
+        self.den
+    }
+
+    #[allow(dead_code)]
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    #[allow(dead_code)]
+    pub fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    #[allow(dead_code)]
+    pub fn ceil(self) -> i128 {
+        -(-self.num).div_euclid(self.den)
+    }
+
+    #[allow(dead_code)]
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self {
+            num: value as i128,
+            den: 1,
+        }
+    }
+}
+
+impl Ord for Rational {
+    // Compare the continued fractions term by term, which unlike
+    // cross-multiplying can never overflow
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = ((self.num, self.den), (other.num, other.den));
+        let mut reversed = false;
+        loop {
+            let (rest_a, rest_b) = (a.0.rem_euclid(a.1), b.0.rem_euclid(b.1));
+            let order = match a.0.div_euclid(a.1).cmp(&b.0.div_euclid(b.1)) {
+                // The larger fraction left over has the smaller reciprocal
+                Ordering::Equal if rest_a != 0 && rest_b != 0 => {
+                    (a, b) = ((a.1, rest_a), (b.1, rest_b));
+                    reversed = !reversed;
+                    continue;
+                }
+                Ordering::Equal => rest_a.cmp(&rest_b),
+                order => order,
+            };
+            return if reversed { order.reverse() } else { order };
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The gcd of numbers too large for `math::gcd`, which is 1 if both are 0
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+fn checked_cross(a: (i128, i128), b: (i128, i128)) -> Result<i128, Error> {
+    let ab = a.0.checked_mul(b.1).ok_or(Error::Overflow)?;
+    let ba = a.1.checked_mul(b.0).ok_or(Error::Overflow)?;
+    ab.checked_sub(ba).ok_or(Error::Overflow)
+}
+
+fn difference(a: Point2, b: Point2) -> (i128, i128) {
+    (a.x as i128 - b.x as i128, a.y as i128 - b.y as i128)
+}
+
+// Twice the area of a simple polygon given by its corners in either order,
+// using the shoelace formula. Twice the area is always an integer.
+#[allow(dead_code)]
+pub fn double_area(polygon: &[Point2]) -> Result<u128, Error> {
+    let corners = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    corners
+        .map(|(a, b)| checked_cross((a.x.into(), a.y.into()), (b.x.into(), b.y.into())))
+        .try_fold(0_i128, |sum, cross| {
+            sum.checked_add(cross?).ok_or(Error::Overflow)
+        })
+        .map(i128::unsigned_abs)
+}
+
+// The number of lattice points on the edges of a polygon
+#[allow(dead_code)]
+pub fn boundary_points(polygon: &[Point2]) -> u128 {
+    let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    edges
+        .map(|(a, b)| math::gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)) as u128)
+        .sum()
+}
+
+// The length of the edges of a polygon when walking along the grid, which is
+// its perimeter if all edges are horizontal or vertical
+#[allow(dead_code)]
+pub fn manhattan_perimeter(polygon: &[Point2]) -> u128 {
+    let edges = polygon.iter().zip(polygon.iter().cycle().skip(1));
+    edges.map(|(a, b)| a.manhattan(*b) as u128).sum()
+}
+
+// The number of lattice points strictly inside a polygon with lattice corners,
+// using Pick's theorem: A = i + b / 2 - 1
+#[allow(dead_code)]
+pub fn interior_points(polygon: &[Point2]) -> Result<u128, Error> {
+    let double_area = double_area(polygon)?;
+    Ok((double_area + 2).saturating_sub(boundary_points(polygon)) / 2)
+}
+
+// The intersection of the line through p1 and p2 and the line through q1 and q2
+#[allow(dead_code)]
+pub fn line_intersection(
+    (p1, p2): (Point2, Point2),
+    (q1, q2): (Point2, Point2),
+) -> Result<Intersection, Error> {
+    intersection((p1, p2), (q1, q2), false)
+}
+
+// The intersection of the segments from p1 to p2 and from q1 to q2, including
+// their end points
+#[allow(dead_code)]
+pub fn segment_intersection(
+    (p1, p2): (Point2, Point2),
+    (q1, q2): (Point2, Point2),
+) -> Result<Intersection, Error> {
+    intersection((p1, p2), (q1, q2), true)
+}
+
+fn intersection(
+    (p1, p2): (Point2, Point2),
+    (q1, q2): (Point2, Point2),
+    segments: bool,
+) -> Result<Intersection, Error> {
+    let r = difference(p2, p1);
+    let s = difference(q2, q1);
+    let pq = difference(q1, p1);
+
+    // p1 + t * r = q1 + u * s for t = (pq x s) / (r x s) and u = (pq x r) / (r x s)
+    let mut denominator = checked_cross(r, s)?;
+    let mut t = checked_cross(pq, s)?;
+    let mut u = checked_cross(pq, r)?;
+    if denominator == 0 {
+        if t != 0 || u != 0 {
+            return Ok(Intersection::None);
+        }
+        if !segments {
+            return Ok(Intersection::Collinear);
+        }
+        return Ok(collinear_overlap((p1, p2), (q1, q2)));
+    }
+    if denominator < 0 {
+        let negate = |n: i128| n.checked_neg().ok_or(Error::Overflow);
+        (denominator, t, u) = (negate(denominator)?, negate(t)?, negate(u)?);
+    }
+    if segments && !((0..=denominator).contains(&t) && (0..=denominator).contains(&u)) {
+        return Ok(Intersection::None);
+    }
+
+    let coordinate = |start: i64, delta: i128| -> Result<Rational, Error> {
+        let offset = t.checked_mul(delta).ok_or(Error::Overflow)?;
+        let num = (start as i128)
+            .checked_mul(denominator)
+            .and_then(|start| start.checked_add(offset))
+            .ok_or(Error::Overflow)?;
+        Ok(Rational::new(num, denominator))
+    };
+    Ok(Intersection::Point(
+        coordinate(p1.x, r.0)?,
+        coordinate(p1.y, r.1)?,
+    ))
+}
+
+// Collinear segments meet in a single point if they only share an end point
+fn collinear_overlap((p1, p2): (Point2, Point2), (q1, q2): (Point2, Point2)) -> Intersection {
+    // Compare the segments along the axis their common line is not
+    // perpendicular to. Two segments of zero length are always "collinear", so
+    // their points are compared along the axis in which they differ
+    let key = |point: Point2| {
+        if p1.x != p2.x || q1.x != q2.x || p1.x != q1.x {
+            point.x
+        } else {
+            point.y
+        }
+    };
+    let (p_low, p_high) = if key(p1) <= key(p2) {
+        (p1, p2)
+    } else {
+        (p2, p1)
+    };
+    let (q_low, q_high) = if key(q1) <= key(q2) {
+        (q1, q2)
+    } else {
+        (q2, q1)
+    };
+    let low = if key(p_low) >= key(q_low) {
+        p_low
+    } else {
+        q_low
+    };
+    let high = if key(p_high) <= key(q_high) {
+        p_high
+    } else {
+        q_high
+    };
+    match key(low).cmp(&key(high)) {
+        std::cmp::Ordering::Greater => Intersection::None,
+        std::cmp::Ordering::Equal => {
+            Intersection::Point(Rational::from(low.x), Rational::from(low.y))
+        }
+        std::cmp::Ordering::Less => Intersection::Collinear,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn points(corners: &[(i64, i64)]) -> Vec<Point2> {
+        corners.iter().copied().map(Point2::from).collect()
+    }
+
+    fn point(x: (i128, i128), y: (i128, i128)) -> Intersection {
+        Intersection::Point(Rational::new(x.0, x.1), Rational::new(y.0, y.1))
+    }
+
+    #[test]
+    fn rationals() {
+        let half = Rational::new(3, 6);
+        assert_eq!((half.num(), half.den()), (1, 2));
+        let negative = Rational::new(-7, 2);
+        assert_eq!((negative.num(), negative.den()), (-7, 2));
+        assert_eq!((negative.floor(), negative.ceil()), (-4, -3));
+        assert_eq!(Rational::new(8, 4).to_integer(), Some(2));
+        assert_eq!(Rational::new(0, 5), Rational::from(0));
+    }
+
+    #[test]
+    fn rational_order() {
+        let ordered = [
+            Rational::from(-2),
+            Rational::new(-3, 2),
+            Rational::new(-1, 3),
+            Rational::from(0),
+            Rational::new(2, 7),
+            Rational::new(1, 3),
+            Rational::new(3, 8),
+            Rational::from(1),
+            Rational::new(i128::MAX, i128::MAX - 1),
+            Rational::new(i128::MAX - 1, i128::MAX - 2),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a:?} against {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn polygon_measures() {
+        // a 4 by 3 rectangle and a right triangle
+        let rectangle = points(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(double_area(&rectangle), Ok(24));
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(manhattan_perimeter(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), Ok(3 * 2));
+
+        let triangle = points(&[(0, 0), (0, 4), (4, 0)]);
+        assert_eq!(double_area(&triangle), Ok(16));
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), Ok(3));
+
+        // the order of the corners does not matter
+        let reversed = triangle.iter().rev().copied().collect::<Vec<_>>();
+        assert_eq!(double_area(&reversed), Ok(16));
+
+        // coordinates far beyond puzzle sizes still fit
+        let big = 1 << 60;
+        let square = points(&[(-big, -big), (big, -big), (big, big), (-big, big)]);
+        assert_eq!(double_area(&square), Ok(2 * (2 * big as u128).pow(2)));
+    }
+
+    #[test]
+    fn lines() {
+        let p = (Point2::new(0, 0), Point2::new(2, 2));
+        let q = (Point2::new(0, 1), Point2::new(1, 1));
+        assert_eq!(line_intersection(p, q), Ok(point((1, 1), (1, 1))));
+        let q = (Point2::new(0, 1), Point2::new(3, 0));
+        assert_eq!(line_intersection(p, q), Ok(point((3, 4), (3, 4))));
+        assert_eq!(segment_intersection(p, q), Ok(point((3, 4), (3, 4))));
+
+        let parallel = (Point2::new(0, 1), Point2::new(1, 2));
+        assert_eq!(line_intersection(p, parallel), Ok(Intersection::None));
+        let same = (Point2::new(5, 5), Point2::new(7, 7));
+        assert_eq!(line_intersection(p, same), Ok(Intersection::Collinear));
+    }
+
+    #[test]
+    fn segments() {
+        let p = (Point2::new(0, 0), Point2::new(4, 0));
+        let crossing = (Point2::new(2, -1), Point2::new(2, 1));
+        assert_eq!(segment_intersection(p, crossing), Ok(point((2, 1), (0, 1))));
+        let touching = (Point2::new(4, 0), Point2::new(5, 5));
+        assert_eq!(segment_intersection(p, touching), Ok(point((4, 1), (0, 1))));
+        let short = (Point2::new(5, -1), Point2::new(5, 1));
+        assert_eq!(segment_intersection(p, short), Ok(Intersection::None));
+        assert!(matches!(
+            line_intersection(p, short),
+            Ok(Intersection::Point(..))
+        ));
+
+        // collinear segments may overlap, touch or be apart
+        let overlap = (Point2::new(3, 0), Point2::new(9, 0));
+        assert_eq!(
+            segment_intersection(p, overlap),
+            Ok(Intersection::Collinear)
+        );
+        let end = (Point2::new(6, 0), Point2::new(4, 0));
+        assert_eq!(segment_intersection(p, end), Ok(point((4, 1), (0, 1))));
+        let apart = (Point2::new(5, 0), Point2::new(9, 0));
+        assert_eq!(segment_intersection(p, apart), Ok(Intersection::None));
+        let vertical = (Point2::new(0, 0), Point2::new(0, 3));
+        let above = (Point2::new(0, 4), Point2::new(0, 5));
+        assert_eq!(
+            segment_intersection(vertical, above),
+            Ok(Intersection::None)
+        );
+
+        // segments of zero length only meet where they are on the other one
+        let dot = |x, y| (Point2::new(x, y), Point2::new(x, y));
+        assert_eq!(
+            segment_intersection(dot(0, 0), dot(5, 0)),
+            Ok(Intersection::None)
+        );
+        assert_eq!(
+            segment_intersection(dot(0, 0), dot(0, 5)),
+            Ok(Intersection::None)
+        );
+        assert_eq!(
+            segment_intersection(dot(0, 0), dot(3, 4)),
+            Ok(Intersection::None)
+        );
+        assert_eq!(
+            segment_intersection(dot(2, 2), dot(2, 2)),
+            Ok(point((2, 1), (2, 1)))
+        );
+        assert_eq!(
+            segment_intersection(dot(2, 0), p),
+            Ok(point((2, 1), (0, 1)))
+        );
+        assert_eq!(
+            segment_intersection(dot(0, 3), vertical),
+            Ok(point((0, 1), (3, 1)))
+        );
+        assert_eq!(
+            segment_intersection(dot(0, 4), vertical),
+            Ok(Intersection::None)
+        );
+        assert_eq!(segment_intersection(dot(2, 1), p), Ok(Intersection::None));
+    }
+
+    #[test]
+    fn puzzle_sized_lines() {
+        // hailstones from 2023 day 24 moved 4e14 along both axes, where the
+        // numerators no longer fit in a u64 before they are reduced
+        let offset = 400_000_000_000_000;
+        let hailstone = |x: i64, y: i64, dx: i64, dy: i64| {
+            let position = Point2::new(offset + x, offset + y);
+            (position, position + Point2::new(dx, dy))
+        };
+        let a = hailstone(19, 13, -300, 200);
+        let b = hailstone(18, 19, -200, -300);
+        let big = offset as i128;
+        let (x, y) = (
+            Rational::new(13 * big + 202, 13),
+            Rational::new(13 * big + 199, 13),
+        );
+        assert_eq!(line_intersection(a, b), Ok(Intersection::Point(x, y)));
+
+        // x is 15 7/13 and y is 15 4/13 past the offset, inside a small test area
+        let (low, high) = (Rational::from(offset + 15), Rational::from(offset + 16));
+        assert!(low <= y && y < x && x <= high);
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        let max = i64::MAX;
+        let square = points(&[(-max, -max), (max, -max), (max, max), (-max, max)]);
+        assert_eq!(double_area(&square), Err(Error::Overflow));
+        assert_eq!(interior_points(&square), Err(Error::Overflow));
+
+        let p = (Point2::new(-max, -max), Point2::new(max, max));
+        let q = (Point2::new(-max, max), Point2::new(max, -max));
+        assert_eq!(line_intersection(p, q), Err(Error::Overflow));
+    }
+}
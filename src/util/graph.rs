@@ -0,0 +1,144 @@
+use crate::util::union_find::UnionFind;
+use std::cmp::Reverse;
+
+// A cut through a graph, with the nodes on one side of it in order
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct Cut {
+    pub weight: u64,
+    pub partition: Vec<usize>,
+}
+
+// The connected components of an undirected graph given as adjacency lists,
+// with their nodes in order and ordered by their smallest node
+#[allow(dead_code)]
+pub fn components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut sets = UnionFind::new(adjacency.len());
+    for (node, neighbours) in adjacency.iter().enumerate() {
+        for &neighbour in neighbours {
+            sets.union(node, neighbour);
+        }
+    }
+    sets.sets()
+}
+
+// A minimum cut of an undirected graph on the nodes 0..n with weighted edges,
+// using the Stoer-Wagner algorithm in O(n^3) time. Ties are broken towards
+// lower nodes, so the same graph always gives the same cut. A graph with less
+// than two nodes cannot be cut.
+#[allow(dead_code)]
+pub fn min_cut(n: usize, edges: &[(usize, usize, u64)]) -> Option<Cut> {
+    let mut weights = vec![vec![0_u64; n]; n];
+    for &(a, b, weight) in edges {
+        if a != b {
+            weights[a][b] += weight;
+            weights[b][a] += weight;
+        }
+    }
+
+    // Nodes are merged together, each keeping track of the original nodes in it
+    let mut merged = (0..n).map(|node| vec![node]).collect::<Vec<_>>();
+    let mut active = (0..n).collect::<Vec<_>>();
+    let mut best: Option<Cut> = None;
+    while active.len() > 1 {
+        // Add the most tightly connected node until all nodes are added
+        let mut connection = vec![0; n];
+        let mut added = vec![false; n];
+        let (mut s, mut t) = (active[0], active[0]);
+        for _ in 0..active.len() {
+            let next = active
+                .iter()
+                .copied()
+                .filter(|node| !added[*node])
+                .max_by_key(|node| (connection[*node], Reverse(*node)))?;
+            added[next] = true;
+            (s, t) = (t, next);
+            for &node in &active {
+                connection[node] += weights[next][node];
+            }
+        }
+
+        // The last node added is cut off from the others by its connection
+        if best.as_ref().is_none_or(|best| connection[t] < best.weight) {
+            let mut partition = merged[t].clone();
+            partition.sort();
+            best = Some(Cut {
+                weight: connection[t],
+                partition,
+            });
+        }
+
+        // Merge the last two nodes added
+        let nodes = std::mem::take(&mut merged[t]);
+        merged[s].extend(nodes);
+        let removed = weights[t].clone();
+        for (node, weight) in removed.into_iter().enumerate() {
+            weights[s][node] += weight;
+            weights[node][s] = weights[s][node];
+        }
+        weights[s][s] = 0;
+        active.retain(|node| *node != t);
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn connected_components() {
+        let adjacency = vec![vec![1], vec![0, 2], vec![1], vec![], vec![5], vec![]];
+        assert_eq!(components(&adjacency), [vec![0, 1, 2], vec![3], vec![4, 5]]);
+        assert!(components(&[]).is_empty());
+    }
+
+    #[test]
+    fn stoer_wagner_example() {
+        // the example graph of the paper by Stoer and Wagner, numbered from zero
+        let edges = [
+            (0, 1, 2),
+            (0, 4, 3),
+            (1, 2, 3),
+            (1, 4, 2),
+            (1, 5, 2),
+            (2, 3, 4),
+            (2, 6, 2),
+            (3, 6, 2),
+            (3, 7, 2),
+            (4, 5, 3),
+            (5, 6, 1),
+            (6, 7, 3),
+        ];
+        let cut = min_cut(8, &edges).unwrap();
+        assert_eq!(cut.weight, 4);
+        let mut other = (0..8)
+            .filter(|node| !cut.partition.contains(node))
+            .collect::<Vec<_>>();
+        other.sort();
+        let mut sides = [cut.partition, other];
+        sides.sort();
+        assert_eq!(sides, [vec![0, 1, 4, 5], vec![2, 3, 6, 7]]);
+    }
+
+    #[test]
+    fn cut_between_cliques() {
+        // two cliques of five nodes joined by three edges
+        let mut edges = Vec::new();
+        for offset in [0, 5] {
+            for a in 0..5 {
+                for b in a + 1..5 {
+                    edges.push((offset + a, offset + b, 1));
+                }
+            }
+        }
+        edges.extend([(0, 5, 1), (1, 6, 1), (2, 7, 1)]);
+        let cut = min_cut(10, &edges).unwrap();
+        assert_eq!(cut.weight, 3);
+        assert_eq!(cut.partition.len(), 5);
+        assert_eq!(min_cut(10, &edges), Some(cut));
+
+        assert_eq!(min_cut(1, &[]), None);
+        assert_eq!(min_cut(2, &[]).map(|cut| cut.weight), Some(0));
+    }
+}
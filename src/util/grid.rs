@@ -0,0 +1,241 @@
+use crate::solver::Error;
+use crate::util::direction::{Dir4, Dir8};
+use crate::util::point::Point2;
+use std::{fmt, ops};
+
+// A rectangular grid of cells, stored row by row and indexed by `(x, y)` with
+// the origin in the top left
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    #[allow(dead_code)]
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Result<Self, Error> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for row in rows {
+            if *width.get_or_insert(row.len()) != row.len() {
+                return Err(Error::Parse(format!(
+                    "row {} has a different width than the rows above",
+                    height + 1
+                )));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    // Parse a character map, with one row per line
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, Error>,
+    ) -> Result<Self, Error> {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&mut cell).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y * self.width + x])
+    }
+
+    // Look up a cell by point, which may lie outside the grid on any side
+    #[allow(dead_code)]
+    pub fn at(&self, point: Point2) -> Option<&T> {
+        let (x, y) = point.try_into().ok()?;
+        self.get(x, y)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` does not accept a size of zero
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.rows().filter_map(move |row| row.get(x))
+    }
+
+    // All positions row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    #[allow(dead_code)]
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(position, cell)| (cell == value).then_some(position))
+    }
+
+    #[allow(dead_code)]
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(position, cell)| (cell == value).then_some(position))
+    }
+
+    // The positions of the horizontal and vertical neighbours inside the grid
+    #[allow(dead_code)]
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(x, y, dir.offset()))
+    }
+
+    // The positions of the neighbours inside the grid, including diagonal ones
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(x, y, dir.offset()))
+    }
+
+    // The position at the offset from a cell, if it lies inside the grid
+    pub fn step(&self, x: usize, y: usize, offset: Point2) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(offset.x.try_into().ok()?)?;
+        let y = y.checked_add_signed(offset.y.try_into().ok()?)?;
+        self.contains(x, y).then_some((x, y))
+    }
+}
+
+impl<T> ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef", Ok).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert!(Grid::parse("ab\nc", Ok).is_err());
+        assert!(Grid::parse("a?", |c| match c {
+            '?' => Err(Error::Parse("unknown cell".to_string())),
+            c => Ok(c),
+        })
+        .is_err());
+        assert_eq!(Grid::parse("", Ok).unwrap().to_string(), "");
+    }
+
+    #[test]
+    fn index_cells() {
+        let mut grid = grid();
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.at(Point2::new(1, 1)), Some(&'e'));
+        assert_eq!(grid.at(Point2::new(-1, 1)), None);
+        grid[(1, 0)] = 'x';
+        assert_eq!(grid[(1, 0)], 'x');
+        assert_eq!(grid.column(1).collect::<String>(), "xe");
+        assert_eq!(grid.rows().nth(1), Some(&['d', 'e', 'f'][..]));
+    }
+
+    #[test]
+    fn find_cells() {
+        let mut grid = Grid::new(3, 3, '.');
+        grid[(2, 0)] = '#';
+        grid[(1, 2)] = '#';
+        assert_eq!(grid.find(&'#'), Some((2, 0)));
+        assert_eq!(grid.find_all(&'#').collect::<Vec<_>>(), [(2, 0), (1, 2)]);
+        assert_eq!(grid.find(&'?'), None);
+        assert_eq!(grid.positions().count(), 9);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        assert_eq!(grid.neighbours4(0, 0).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(
+            grid.neighbours8(0, 0).collect::<Vec<_>>(),
+            [(1, 0), (1, 1), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+        assert_eq!(grid.neighbours4(1, 0).count(), 3);
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+
+// The half-open range of integers from `start` up to but not including `end`
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+// A set of integers stored as sorted intervals, which are never empty and
+// never overlap or touch
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl Interval {
+    // Every interval that ends before it starts is empty
+    pub fn new(start: i64, end: i64) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start.saturating_add(len))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> u64 {
+        self.end.abs_diff(self.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    #[allow(dead_code)]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    pub fn shift(&self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Merge the interval with all intervals it overlaps or touches
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, other| {
+                Interval::new(merged.start.min(other.start), merged.end.max(other.end))
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // The number of integers in the set
+    #[allow(dead_code)]
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    #[allow(dead_code)]
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for interval in &other.intervals {
+            union.insert(*interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        // Walk through both sorted lists at the same time
+        let mut intersection = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intersection.insert(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        intersection
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = Self::new();
+        let mut removed = other.intervals.iter().peekable();
+        for interval in &self.intervals {
+            let mut start = interval.start;
+            while let Some(hole) = removed.next_if(|hole| hole.end <= interval.end) {
+                difference.insert(Interval::new(start, hole.start.min(interval.end)));
+                start = start.max(hole.end);
+            }
+            // A hole sticking out past this interval may cut into the next one too
+            if let Some(hole) = removed.peek() {
+                difference.insert(Interval::new(start, hole.start.min(interval.end)));
+            } else {
+                difference.insert(Interval::new(start, interval.end));
+            }
+        }
+        difference
+    }
+
+    // Split the set into the parts inside and outside the interval
+    pub fn split(&self, at: Interval) -> (Self, Self) {
+        let at = Self::from_iter([at]);
+        (self.intersection(&at), self.difference(&at))
+    }
+
+    // Move every part of the set inside one of the ranges by its offset, like
+    // a lookup table that leaves values outside its ranges unchanged
+    pub fn map_ranges(&self, ranges: &[(Interval, i64)]) -> Self {
+        let mut rest = self.clone();
+        let mut mapped = Self::new();
+        for &(range, offset) in ranges {
+            let (inside, outside) = rest.split(range);
+            mapped = mapped.union(&inside.shift(offset));
+            rest = outside;
+        }
+        mapped.union(&rest)
+    }
+
+    pub fn shift(&self, offset: i64) -> Self {
+        Self {
+            intervals: self
+                .intervals
+                .iter()
+                .map(|interval| interval.shift(offset))
+                .collect(),
+        }
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        for interval in intervals {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    // Compare against a set of single values in a small window
+    fn values(set: &IntervalSet) -> Vec<i64> {
+        (-20..40).filter(|value| set.contains(*value)).collect()
+    }
+
+    #[test]
+    fn intervals() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3) && interval.contains(6));
+        assert!(!interval.contains(7) && !interval.contains(2));
+        assert!(Interval::new(5, 2).is_empty());
+        assert_eq!(Interval::with_len(3, 4), interval);
+        assert!(interval.overlaps(&Interval::new(6, 8)));
+        assert!(!interval.overlaps(&Interval::new(7, 8)));
+        assert_eq!(interval.shift(-3), Interval::new(0, 4));
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+    }
+
+    #[test]
+    fn insert_merges() {
+        let mut intervals = set(&[(0, 2), (5, 7), (10, 12)]);
+        intervals.insert(Interval::new(2, 3));
+        assert_eq!(intervals, set(&[(0, 3), (5, 7), (10, 12)]));
+        intervals.insert(Interval::new(4, 10));
+        assert_eq!(intervals, set(&[(0, 3), (4, 12)]));
+        intervals.insert(Interval::new(8, 8));
+        assert_eq!(intervals.intervals().len(), 2);
+        intervals.insert(Interval::new(-5, 20));
+        assert_eq!(intervals.intervals(), [Interval::new(-5, 20)]);
+        assert_eq!(intervals.len(), 25);
+        assert_eq!((intervals.min(), intervals.max()), (Some(-5), Some(19)));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 5), (8, 12), (20, 25)]);
+        let b = set(&[(-3, 0), (4, 9), (11, 21), (24, 30)]);
+        let in_a = values(&a);
+        let in_b = values(&b);
+        let expected = |keep: fn(bool, bool) -> bool| -> Vec<i64> {
+            (-20..40)
+                .filter(|value| keep(in_a.contains(value), in_b.contains(value)))
+                .collect()
+        };
+        assert_eq!(values(&a.union(&b)), expected(|a, b| a || b));
+        assert_eq!(values(&a.intersection(&b)), expected(|a, b| a && b));
+        assert_eq!(values(&a.difference(&b)), expected(|a, b| a && !b));
+        assert_eq!(values(&b.difference(&a)), expected(|a, b| b && !a));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+        assert_eq!(a.union(&a), a);
+    }
+
+    #[test]
+    fn difference_with_wide_holes() {
+        let a = set(&[(0, 3), (5, 8), (10, 13)]);
+        assert_eq!(a.difference(&set(&[(2, 11)])), set(&[(0, 2), (11, 13)]));
+        assert_eq!(a.difference(&set(&[(-1, 0), (13, 14)])), a);
+        assert_eq!(a.difference(&set(&[(3, 5), (8, 10)])), a);
+        assert_eq!(
+            a.difference(&set(&[(0, 1), (7, 8)])),
+            set(&[(1, 3), (5, 7), (10, 13)])
+        );
+    }
+
+    #[test]
+    fn split_and_map() {
+        let seeds = set(&[(79, 93), (55, 68)]);
+        let (inside, outside) = seeds.split(Interval::new(60, 80));
+        assert_eq!(inside, set(&[(60, 68), (79, 80)]));
+        assert_eq!(outside, set(&[(55, 60), (80, 93)]));
+
+        // the seed-to-soil map of the 2023 day 5 example
+        let ranges = [
+            (Interval::with_len(98, 2), 50 - 98),
+            (Interval::with_len(50, 48), 52 - 50),
+        ];
+        assert_eq!(seeds.map_ranges(&ranges), set(&[(57, 70), (81, 95)]));
+        assert_eq!(
+            set(&[(97, 101)]).map_ranges(&ranges),
+            set(&[(50, 52), (99, 101)])
+        );
+    }
+}
@@ -0,0 +1,214 @@
+#[allow(dead_code)]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[allow(dead_code)]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    checked_lcm(a, b).expect("lcm overflows")
+}
+
+#[allow(dead_code)]
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// The gcd of no numbers is 0, which divides everything
+#[allow(dead_code)]
+pub fn gcd_all(numbers: &[u64]) -> u64 {
+    numbers.iter().fold(0, |gcd_so_far, n| gcd(gcd_so_far, *n))
+}
+
+#[allow(dead_code)]
+pub fn checked_lcm_all(numbers: &[u64]) -> Option<u64> {
+    numbers
+        .iter()
+        .try_fold(1, |lcm_so_far, n| checked_lcm(lcm_so_far, *n))
+}
+
+// The gcd of a and b with x and y such that a * x + b * y = gcd
+#[allow(dead_code)]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a as i128, b as i128);
+    let (mut x0, mut x1) = (1_i128, 0_i128);
+    let (mut y0, mut y1) = (0_i128, 1_i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (r0, x0, y0) = (-r0, -x0, -y0);
+    }
+    // The coefficients are bounded by the inputs, except for `i64::MIN`
+    (r0 as i64, x0 as i64, y0 as i64)
+}
+
+// The x in 0..m such that a * x = 1 modulo m, if a and m are coprime
+#[allow(dead_code)]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (gcd, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (gcd == 1).then(|| x.rem_euclid(m))
+}
+
+#[allow(dead_code)]
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    if m == 1 {
+        return 0;
+    }
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// Solve x = r_i modulo m_i for all congruences, returning x and the lcm of the
+// moduli, which need not be coprime. There is no solution if the congruences
+// contradict each other or the lcm overflows.
+#[allow(dead_code)]
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0_i64, 1_i64), |(x, m), &(r, n)| {
+            if n <= 0 {
+                return None;
+            }
+            // x + m * k = r modulo n, so m * k = r - x modulo n
+            let (g, p, _) = extended_gcd(m, n);
+            let diff = r as i128 - x as i128;
+            if diff % g as i128 != 0 {
+                return None;
+            }
+            let lcm = i64::try_from(m as i128 / g as i128 * n as i128).ok()?;
+            let step = n as i128 / g as i128;
+            let k = (diff / g as i128 % step * p as i128).rem_euclid(step);
+            let x = (x as i128 + m as i128 * k).rem_euclid(lcm as i128);
+            Some((x as i64, lcm))
+        })
+}
+
+// The largest integer whose square is at most n
+#[allow(dead_code)]
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+#[allow(dead_code)]
+pub fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
+}
+
+// The inclusive range of integers x for which a * x^2 + b * x + c < 0, where
+// a > 0, or `None` if there are none or they do not fit
+pub fn quadratic_below_zero(a: i64, b: i64, c: i64) -> Option<(i64, i64)> {
+    if a <= 0 {
+        return None;
+    }
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let discriminant = b
+        .checked_mul(b)?
+        .checked_sub(4_i128.checked_mul(a)?.checked_mul(c)?)?;
+    if discriminant <= 0 {
+        return None;
+    }
+    let root = i128::try_from(u128::try_from(discriminant).ok()?.isqrt()).ok()?;
+    let f = |x: i128| -> Option<i128> {
+        a.checked_mul(x)?
+            .checked_add(b)?
+            .checked_mul(x)?
+            .checked_add(c)
+    };
+
+    // Start just outside the real roots, then nudge inwards past the boundary
+    let mut low = (-b - root).div_euclid(2 * a) - 1;
+    let mut high = (-b + root).div_euclid(2 * a) + 2;
+    while low <= high && f(low)? >= 0 {
+        low += 1;
+    }
+    while low <= high && f(high)? >= 0 {
+        high -= 1;
+    }
+    (low <= high).then_some((i64::try_from(low).ok()?, i64::try_from(high).ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gcds_and_lcms() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(gcd_all(&[12, 18, 27]), 3);
+        assert_eq!(gcd_all(&[]), 0);
+        assert_eq!(checked_lcm_all(&[2, 3, 4, 5]), Some(60));
+        assert_eq!(checked_lcm_all(&[u64::MAX, u64::MAX - 1]), None);
+        assert_eq!(checked_lcm(0, 7), Some(0));
+    }
+
+    #[test]
+    fn euclid_and_inverses() {
+        for (a, b) in [(240, 46), (-240, 46), (7, 0), (0, -7), (17, 17)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g);
+        }
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn chinese_remainders() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        let big = (1 << 40) + 15;
+        assert_eq!(crt(&[(1, big), (2, big - 2)]).map(|(_, m)| m), None);
+    }
+
+    #[test]
+    fn roots() {
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(exact_sqrt(144), Some(12));
+        assert_eq!(exact_sqrt(143), None);
+
+        // x^2 - 7x + 9 < 0 for 2..=5, and x^2 - 30x + 200 is 0 at 10 and 20
+        assert_eq!(quadratic_below_zero(1, -7, 9), Some((2, 5)));
+        assert_eq!(quadratic_below_zero(1, -30, 200), Some((11, 19)));
+        assert_eq!(quadratic_below_zero(1, -4, 4), None);
+        assert_eq!(quadratic_below_zero(2, 0, -1), Some((0, 0)));
+        assert_eq!(quadratic_below_zero(-1, 0, 1), None);
+        for t in 0..40 {
+            for s in 0..t * t / 4 + 2 {
+                let count = (0..=t).filter(|h| h * (t - h) > s).count() as i64;
+                let range = quadratic_below_zero(1, -t, s);
+                assert_eq!(range.map_or(0, |(low, high)| high - low + 1), count);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::{cell::Cell, collections::HashMap, hash::Hash};
+
+thread_local! {
+    // Bumped before every solve, so caches of `memoise!` functions are only
+    // reused within a single solve on the same thread
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+}
+
+// Forget everything `memoise!` functions cached on this thread
+pub fn reset() {
+    GENERATION.with(|generation| generation.set(generation.get() + 1));
+}
+
+#[allow(dead_code)]
+pub fn generation() -> u64 {
+    GENERATION.with(Cell::get)
+}
+
+// A cache for a recursive function, created inside the solver so it lives
+// exactly as long as one solve. The function receives the cache to make its
+// recursive calls through, e.g.
+//
+//     fn paths(memo: &mut Memo<(u64, u64), u64>, x: u64, y: u64) -> u64 {
+//         memo.get_or_insert_with((x, y), |memo| match (x, y) {
+//             (0, _) | (_, 0) => 1,
+//             _ => paths(memo, x - 1, y) + paths(memo, x, y - 1),
+//         })
+//     }
+#[allow(dead_code)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Define a function whose results are cached by its arguments until the next
+// solve starts, for recursive functions that are awkward to pass a `Memo` to.
+// The arguments must be owned values that can be cloned and hashed. No puzzle
+// needs it yet, so only the tests use it.
+#[cfg_attr(not(test), allow(unused_macros))]
+macro_rules! memoise {
+    ($vis:vis fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block) => {
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            thread_local! {
+                static CACHE: ::std::cell::RefCell<(u64, ::std::collections::HashMap<($($ty,)*), $ret>)> =
+                    ::std::cell::RefCell::new((0, ::std::collections::HashMap::new()));
+            }
+            let key = ($($arg.clone(),)*);
+            let cached = CACHE.with(|cache| {
+                let mut cache = cache.borrow_mut();
+                let generation = $crate::util::memo::generation();
+                if cache.0 != generation {
+                    *cache = (generation, ::std::collections::HashMap::new());
+                }
+                cache.1.get(&key).cloned()
+            });
+            if let Some(value) = cached {
+                return value;
+            }
+            let value: $ret = (move || $body)();
+            CACHE.with(|cache| cache.borrow_mut().1.insert(key, value.clone()));
+            value
+        }
+    };
+}
+
+#[cfg_attr(not(test), allow(unused_imports))]
+pub(crate) use memoise;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    fn paths(memo: &mut Memo<(u64, u64), u64>, x: u64, y: u64) -> u64 {
+        memo.get_or_insert_with((x, y), |memo| match (x, y) {
+            (0, _) | (_, 0) => 1,
+            _ => paths(memo, x - 1, y) + paths(memo, x, y - 1),
+        })
+    }
+
+    #[test]
+    fn memo_struct() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(&mut memo, 16, 16), 601_080_390);
+        assert_eq!(memo.len(), 17 * 17 - 1);
+    }
+
+    thread_local! {
+        static CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    crate::util::memo::memoise! {
+        fn fibonacci(n: u64) -> u64 {
+            CALLS.with(|calls| calls.set(calls.get() + 1));
+            if n < 2 {
+                return n;
+            }
+            fibonacci(n - 1) + fibonacci(n - 2)
+        }
+    }
+
+    #[test]
+    fn memoise_macro() {
+        reset();
+        assert_eq!(fibonacci(90), 2_880_067_194_370_816_120);
+        assert_eq!(CALLS.with(Cell::get), 91);
+        assert_eq!(fibonacci(50), 12_586_269_025);
+        assert_eq!(CALLS.with(Cell::get), 91);
+
+        // a new solve starts with an empty cache
+        reset();
+        assert_eq!(fibonacci(10), 55);
+        assert_eq!(CALLS.with(Cell::get), 91 + 11);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod cycle;
+pub mod direction;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod pattern;
+pub mod point;
+pub mod search;
+pub mod union_find;
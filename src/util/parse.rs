@@ -0,0 +1,217 @@
+use crate::solver::Error;
+use std::{any, fmt, str::FromStr};
+
+// A parse error at a line and column of the input, both counting from one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+// The whole puzzle input, which every piece handed to its methods must be a
+// slice of, so errors can point at where in the input they occurred
+#[derive(Copy, Clone, Debug)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input }
+    }
+
+    // The lines of the input without line endings, also stripping a `\r`
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        // `str::lines` does not yield the empty line after a final `\n` either
+        let source = *self;
+        self.input
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .filter(move |line| {
+                !(line.is_empty() && source.offset(line) == Some(source.input.len()))
+            })
+    }
+
+    // The blocks of lines separated by blank lines, without their trailing
+    // line ending
+    pub fn blocks(&self) -> impl Iterator<Item = &'a str> {
+        let input = self.input;
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+            let first = lines.next()?;
+            let mut last = first;
+            while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+                last = line;
+            }
+            let start = first.as_ptr() as usize - input.as_ptr() as usize;
+            let end = last.as_ptr() as usize - input.as_ptr() as usize + last.len();
+            Some(&input[start..end])
+        })
+    }
+
+    pub fn error(&self, at: &str, message: impl Into<String>) -> ParseError {
+        // Pieces that are not part of the input are reported at its start
+        let offset = self.offset(at).unwrap_or(0);
+        let before = &self.input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .chars()
+            .rev()
+            .take_while(|char| *char != '\n')
+            .count()
+            + 1;
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("expected {prefix:?}")))
+    }
+
+    pub fn split_once(
+        &self,
+        text: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        text.split_once(delimiter)
+            .ok_or_else(|| self.error(text, format!("expected {delimiter:?}")))
+    }
+
+    pub fn parse<T: FromStr>(&self, text: &'a str) -> Result<T, ParseError> {
+        text.trim().parse().map_err(|_| {
+            let what = any::type_name::<T>();
+            self.error(text.trim_start(), format!("expected {what}, got {text:?}"))
+        })
+    }
+
+    // The non-negative integers of the text, separated by whitespace
+    pub fn unsigned<T: FromStr>(&self, text: &'a str) -> Result<Vec<T>, ParseError> {
+        self.integers(text, false)
+    }
+
+    // The integers of the text, separated by whitespace, which may be negative
+    pub fn signed<T: FromStr>(&self, text: &'a str) -> Result<Vec<T>, ParseError> {
+        self.integers(text, true)
+    }
+
+    // Exactly N integers of the text, e.g. the three numbers of a range mapping
+    pub fn array<T: FromStr + fmt::Debug, const N: usize>(
+        &self,
+        text: &'a str,
+    ) -> Result<[T; N], ParseError> {
+        let integers = self.signed(text)?;
+        let count = integers.len();
+        integers
+            .try_into()
+            .map_err(|_| self.error(text, format!("expected {N} integers, found {count}")))
+    }
+
+    // Two values around a delimiter, e.g. `x=3` into `("x", 3)`
+    #[allow(dead_code)]
+    pub fn pair<A: FromStr, B: FromStr>(
+        &self,
+        text: &'a str,
+        delimiter: &str,
+    ) -> Result<(A, B), ParseError> {
+        let (a, b) = self.split_once(text, delimiter)?;
+        Ok((self.parse(a)?, self.parse(b)?))
+    }
+
+    // Anything else between the integers is an error rather than skipped, so
+    // malformed input is reported instead of silently giving a wrong answer
+    fn integers<T: FromStr>(&self, text: &'a str, signed: bool) -> Result<Vec<T>, ParseError> {
+        text.split_whitespace()
+            .map(|word| match word.strip_prefix('-') {
+                Some(_) if !signed => {
+                    Err(self.error(word, format!("expected {word:?} to be non-negative")))
+                }
+                _ => self.parse(word),
+            })
+            .collect()
+    }
+
+    fn offset(&self, text: &str) -> Option<usize> {
+        let offset = (text.as_ptr() as usize).checked_sub(self.input.as_ptr() as usize)?;
+        (offset + text.len() <= self.input.len()).then_some(offset)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lines_and_blocks() {
+        let source = Source::new("a\r\nb\r\n\r\n\r\nc\n \nd\ne\n");
+        assert_eq!(
+            source.lines().collect::<Vec<_>>(),
+            ["a", "b", "", "", "c", " ", "d", "e"]
+        );
+        assert_eq!(source.blocks().collect::<Vec<_>>(), ["a\r\nb", "c", "d\ne"]);
+        assert_eq!(Source::new("").blocks().count(), 0);
+    }
+
+    #[test]
+    fn integers() {
+        let source = Source::new("12  3\t45 -7 8 x9");
+        assert_eq!(
+            source.unsigned::<u32>(&source.input[..9]),
+            Ok(vec![12, 3, 45])
+        );
+        assert_eq!(
+            source.signed::<i32>(&source.input[..14]),
+            Ok(vec![12, 3, 45, -7, 8])
+        );
+        assert_eq!(source.array::<i64, 2>(&source.input[..5]), Ok([12, 3]));
+
+        let error = source.array::<i64, 3>(&source.input[4..]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 15));
+        assert_eq!(error.message, "expected i64, got \"x9\"");
+        let error = source.array::<i64, 3>(&source.input[4..9]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert_eq!(error.message, "expected 3 integers, found 2");
+
+        // Nothing is skipped, so stray text or a sign is an error
+        let error = source.unsigned::<i32>(&source.input[9..]).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 10));
+        assert_eq!(error.message, "expected \"-7\" to be non-negative");
+        assert!(source.unsigned::<u32>(source.input).is_err());
+    }
+
+    #[test]
+    fn error_positions() {
+        let input = "seeds: 1 2\nfoo: 99999999999\n";
+        let source = Source::new(input);
+        let line = source.lines().nth(1).unwrap();
+        let error = source.unsigned::<u32>(&line[4..]).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(
+            Error::from(source.strip_prefix(line, "bar:").unwrap_err()),
+            Error::Parse("line 2, column 1: expected \"bar:\"".to_string())
+        );
+        assert_eq!(source.pair::<String, u8>("x=300", "=").unwrap_err().line, 1);
+        assert_eq!(source.pair("x=3", "="), Ok(("x".to_string(), 3_u8)));
+    }
+}
@@ -0,0 +1,150 @@
+// Implement `FromStr` for a struct from a pattern of literal text and fields,
+// e.g. `pattern!(Game = "Game " {id} ": " {rest})`. Every field is parsed
+// from the text up to the next literal with its own `FromStr` implementation,
+// or with a function returning `Result<_, Error>` as in `{cubes: parse_cubes}`.
+// The fields of the pattern must be all the fields of the struct.
+macro_rules! pattern {
+    ($name:ident = $($item:tt)+) => {
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::solver::Error;
+
+            fn from_str(line: &str) -> Result<Self, Self::Err> {
+                let mut rest = line;
+                $crate::util::pattern::pattern!(@items line rest [] $($item)+)
+            }
+        }
+    };
+
+    // Nothing may follow the last item
+    (@items $line:ident $rest:ident [$($field:ident)*]) => {{
+        if !$rest.is_empty() {
+            return Err($crate::util::pattern::error($line, $rest, "unexpected text"));
+        }
+        Ok(Self { $($field),* })
+    }};
+
+    (@items $line:ident $rest:ident [$($done:ident)*] $literal:literal $($tail:tt)*) => {{
+        $rest = $rest.strip_prefix($literal).ok_or_else(|| {
+            $crate::util::pattern::error($line, $rest, &format!("expected {:?}", $literal))
+        })?;
+        $crate::util::pattern::pattern!(@items $line $rest [$($done)*] $($tail)*)
+    }};
+
+    // A field followed by a literal ends where the literal first occurs
+    (@items $line:ident $rest:ident [$($done:ident)*]
+        {$field:ident $(: $parser:expr)?} $literal:literal $($tail:tt)*) => {{
+        let Some((text, tail)) = $rest.split_once($literal) else {
+            return Err($crate::util::pattern::error(
+                $line,
+                $rest,
+                &format!("expected {:?} after {}", $literal, stringify!($field)),
+            ));
+        };
+        let $field = $crate::util::pattern::pattern!(@field $line text $field $($parser)?);
+        $rest = tail;
+        $crate::util::pattern::pattern!(@items $line $rest [$($done)* $field] $($tail)*)
+    }};
+
+    // A field at the end takes the rest of the line
+    (@items $line:ident $rest:ident [$($done:ident)*] {$field:ident $(: $parser:expr)?}) => {{
+        let $field = $crate::util::pattern::pattern!(@field $line $rest $field $($parser)?);
+        $rest = "";
+        $crate::util::pattern::pattern!(@items $line $rest [$($done)* $field])
+    }};
+
+    (@field $line:ident $text:ident $field:ident) => {
+        $text.parse().map_err(|_| {
+            $crate::util::pattern::error(
+                $line,
+                $text,
+                &format!("invalid {} {:?}", stringify!($field), $text),
+            )
+        })?
+    };
+
+    (@field $line:ident $text:ident $field:ident $parser:expr) => {
+        $parser($text)?
+    };
+}
+
+pub(crate) use pattern;
+
+use crate::solver::Error;
+
+// Point at the column where a piece of the line starts
+pub fn error(line: &str, at: &str, message: &str) -> Error {
+    let column = (at.as_ptr() as usize).saturating_sub(line.as_ptr() as usize) + 1;
+    Error::Parse(format!("{message} at column {column} of {line:?}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Move {
+        count: usize,
+        from: char,
+        to: char,
+    }
+
+    pattern!(Move = "move " {count} " from " {from} " to " {to});
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Entry {
+        name: String,
+        values: Vec<i32>,
+    }
+
+    fn values(text: &str) -> Result<Vec<i32>, Error> {
+        text.split(',')
+            .map(|value| value.parse().map_err(|_| Error::Parse(value.to_string())))
+            .collect()
+    }
+
+    pattern!(Entry = {name} ": " {values: values});
+
+    #[test]
+    fn parse_fields() {
+        assert_eq!(
+            "move 12 from a to b".parse(),
+            Ok(Move {
+                count: 12,
+                from: 'a',
+                to: 'b'
+            })
+        );
+        assert_eq!(
+            "x: 1,-2".parse(),
+            Ok(Entry {
+                name: "x".to_string(),
+                values: vec![1, -2]
+            })
+        );
+        assert_eq!(
+            "x: 1,y".parse::<Entry>(),
+            Err(Error::Parse("y".to_string()))
+        );
+    }
+
+    #[test]
+    fn report_errors() {
+        let error = |message: &str| Err(Error::Parse(message.to_string()));
+        assert_eq!(
+            "mv 1 from a to b".parse::<Move>(),
+            error("expected \"move \" at column 1 of \"mv 1 from a to b\"")
+        );
+        assert_eq!(
+            "move x from a to b".parse::<Move>(),
+            error("invalid count \"x\" at column 6 of \"move x from a to b\"")
+        );
+        assert_eq!(
+            "move 1 from a".parse::<Move>(),
+            error("expected \" to \" after from at column 13 of \"move 1 from a\"")
+        );
+        assert_eq!(
+            "move 1 from a to bc".parse::<Move>(),
+            error("invalid to \"bc\" at column 18 of \"move 1 from a to bc\"")
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::ops;
+
+// A position or vector on a 2D grid, with y pointing down like the rows of a
+// puzzle input
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(dead_code)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    // Rotate a quarter turn around the origin, as seen on the screen
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl Point3 {
+    #[allow(dead_code)]
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    #[allow(dead_code)]
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    #[allow(dead_code)]
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+}
+
+// Conversions from and to grid indices, which fail if a coordinate does not fit
+impl TryFrom<(usize, usize)> for Point2 {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Self::new(x.try_into()?, y.try_into()?))
+    }
+}
+
+impl TryFrom<Point2> for (usize, usize) {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point2) -> Result<Self, Self::Error> {
+        Ok((point.x.try_into()?, point.y.try_into()?))
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+// Implement the arithmetic operators component-wise for both point types
+macro_rules! arithmetic {
+    ($point:ident { $($field:ident),+ }) => {
+        impl ops::Add for $point {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl ops::Sub for $point {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl ops::Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, factor: i64) -> Self {
+                Self { $($field: self.$field * factor),+ }
+            }
+        }
+
+        impl ops::Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl ops::AddAssign for $point {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl ops::SubAssign for $point {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+arithmetic!(Point2 { x, y });
+arithmetic!(Point3 { x, y, z });
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut point = Point2::new(1, 2);
+        point += Point2::new(3, -4);
+        assert_eq!(point, Point2::new(4, -2));
+        assert_eq!(point - Point2::new(4, 4), Point2::new(0, -6));
+        assert_eq!(-point * 2, Point2::new(-8, 4));
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1),
+            Point3::new(2, 3, 4)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point2::new(1, -2).manhattan(Point2::ORIGIN), 3);
+        assert_eq!(
+            Point2::new(i64::MIN, 0).manhattan(Point2::new(i64::MAX, 0)),
+            u64::MAX
+        );
+        assert_eq!(Point3::new(1, -2, 3).manhattan(Point3::new(0, 0, 0)), 6);
+    }
+
+    #[test]
+    fn rotations() {
+        let point = Point2::new(2, 1);
+        assert_eq!(point.rotate_left(), Point2::new(1, -2));
+        assert_eq!(point.rotate_right().rotate_left(), point);
+        assert_eq!(
+            point
+                .rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            point
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Point2::try_from((3_usize, 4_usize)), Ok(Point2::new(3, 4)));
+        assert!(Point2::try_from((usize::MAX, 0)).is_err());
+        assert_eq!(<(usize, usize)>::try_from(Point2::new(3, 4)), Ok((3, 4)));
+        assert!(<(usize, usize)>::try_from(Point2::new(-1, 4)).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+// A path found by a search, from the start up to and including the goal
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(dead_code)]
+pub struct Path<S> {
+    pub states: Vec<S>,
+    pub cost: u64,
+    // The number of states taken from the queue, to compare heuristics
+    pub visited: usize,
+}
+
+// Every state found so far with the index of the state it was reached from
+struct Tree<S> {
+    nodes: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Tree<S> {
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.nodes.push((state, parent));
+        self.nodes.len() - 1
+    }
+
+    fn path(&self, mut index: usize, cost: u64, visited: usize) -> Path<S> {
+        let mut states = vec![self.nodes[index].0.clone()];
+        while let Some(parent) = self.nodes[index].1 {
+            states.push(self.nodes[parent].0.clone());
+            index = parent;
+        }
+        states.reverse();
+        Path {
+            states,
+            cost,
+            visited,
+        }
+    }
+}
+
+// Find a path with the fewest steps, stopping as soon as a goal is reached
+#[allow(dead_code)]
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut tree = Tree { nodes: Vec::new() };
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(tree.push(start, None), 0)]);
+    let mut visited = 0;
+    while let Some((index, steps)) = queue.pop_front() {
+        visited += 1;
+        let state = tree.nodes[index].0.clone();
+        if is_goal(&state) {
+            return Some(tree.path(index, steps, visited));
+        }
+        for next in neighbours(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back((tree.push(next, Some(index)), steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// The number of steps to every state reachable from the start
+#[allow(dead_code)]
+pub fn bfs_distances<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> HashMap<S, u64>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in neighbours(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+// Find a cheapest path, where neighbours come with the cost of the step to them
+#[allow(dead_code)]
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, neighbours, |_| 0, is_goal)
+}
+
+// Like Dijkstra, but exploring states in order of their cost so far plus the
+// heuristic, which must never overestimate the remaining cost
+#[allow(dead_code)]
+pub fn astar<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut tree = Tree { nodes: Vec::new() };
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, tree.push(start, None)))]);
+    let mut visited = 0;
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let state = tree.nodes[index].0.clone();
+
+        // Skip states that were queued again with a lower cost in the meantime
+        if costs.get(&state).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        visited += 1;
+        if is_goal(&state) {
+            return Some(tree.path(index, cost, visited));
+        }
+        for (next, step) in neighbours(&state) {
+            let Some(next_cost) = cost.checked_add(step) else {
+                continue;
+            };
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            let priority = next_cost.saturating_add(heuristic(&next));
+            queue.push(Reverse((priority, next_cost, tree.push(next, Some(index)))));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::grid::Grid;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##...#E";
+
+    fn maze() -> (Grid<char>, (usize, usize), (usize, usize)) {
+        let grid = Grid::parse(MAZE, Ok).unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        (grid, start, end)
+    }
+
+    #[test]
+    fn bfs_on_grid() {
+        let (grid, start, end) = maze();
+        let open = |&(x, y): &(usize, usize)| {
+            grid.neighbours4(x, y)
+                .filter(|&(x, y)| grid[(x, y)] != '#')
+                .collect::<Vec<_>>()
+        };
+        let path = bfs(start, open, |position| *position == end).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.states.len(), 13);
+        assert_eq!(path.states.first(), Some(&start));
+        assert_eq!(path.states.last(), Some(&end));
+        assert!(path
+            .states
+            .windows(2)
+            .all(|pair| grid.neighbours4(pair[0].0, pair[0].1).any(|p| p == pair[1])));
+
+        let distances = bfs_distances(start, open);
+        assert_eq!(distances.get(&end), Some(&12));
+        assert_eq!(distances.len(), 32 - grid.find_all(&'#').count());
+        assert_eq!(bfs(start, open, |position| grid[*position] == '#'), None);
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        // walking right is cheap, every other step is expensive
+        let (grid, start, end) = maze();
+        let neighbours = |&(x, y): &(usize, usize)| {
+            grid.neighbours4(x, y)
+                .filter(|&(x, y)| grid[(x, y)] != '#')
+                .map(move |(nx, ny)| ((nx, ny), if nx > x { 1 } else { 5 }))
+                .collect::<Vec<_>>()
+        };
+        let is_goal = |position: &(usize, usize)| *position == end;
+        let manhattan = |&(x, y): &(usize, usize)| (x.abs_diff(end.0) + y.abs_diff(end.1)) as u64;
+
+        let slow = dijkstra(start, neighbours, is_goal).unwrap();
+        let fast = astar(start, neighbours, manhattan, is_goal).unwrap();
+        assert_eq!(slow.cost, fast.cost);
+        assert_eq!(slow.cost, 7 + 5 * 5);
+        assert!(fast.visited <= slow.visited);
+    }
+
+    #[test]
+    fn early_exit_on_state_space() {
+        // reach 100 from 1 by doubling or adding one, with visits stopping early
+        let neighbours = |n: &u64| [(n * 2, 1), (n + 1, 1)];
+        let path = dijkstra(1, neighbours, |n| *n == 100).unwrap();
+        assert_eq!(path.states, [1, 2, 3, 6, 12, 24, 25, 50, 100]);
+        assert_eq!(path.cost, 8);
+        assert!(path.visited < 200);
+    }
+}
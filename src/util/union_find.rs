@@ -0,0 +1,116 @@
+// Disjoint sets of the elements 0..n, merged by size with paths compressed on
+// lookup, so both operations take nearly constant time
+#[derive(Clone, Debug)]
+#[allow(dead_code)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    #[allow(dead_code)]
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+            count: n,
+        }
+    }
+
+    // The representative of the set containing x
+    #[allow(dead_code)]
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut x = x;
+        while self.parents[x] != root {
+            (x, self.parents[x]) = (self.parents[x], root);
+        }
+        root
+    }
+
+    // Merge the sets containing a and b, returning whether they were separate
+    #[allow(dead_code)]
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.count -= 1;
+        true
+    }
+
+    #[allow(dead_code)]
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // The size of the set containing x
+    #[allow(dead_code)]
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+
+    // The number of disjoint sets
+    #[allow(dead_code)]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // All sets with their elements in order, ordered by their smallest element
+    #[allow(dead_code)]
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut sets: Vec<Vec<usize>> = Vec::new();
+        let mut index: Vec<Option<usize>> = vec![None; self.parents.len()];
+        for x in 0..self.parents.len() {
+            let root = self.find(x);
+            match index[root] {
+                Some(i) => sets[i].push(x),
+                None => {
+                    index[root] = Some(sets.len());
+                    sets.push(vec![x]);
+                }
+            }
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn merge_sets() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 3));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+        assert!(sets.same(3, 0));
+        assert!(!sets.same(2, 0));
+        assert_eq!(sets.size(4), 4);
+        assert_eq!(sets.size(5), 1);
+        assert_eq!(sets.count(), 3);
+        assert_eq!(sets.sets(), [vec![0, 1, 3, 4], vec![2], vec![5]]);
+    }
+
+    #[test]
+    fn long_chains_compress() {
+        let mut sets = UnionFind::new(100_000);
+        for x in 1..100_000 {
+            sets.union(x - 1, x);
+        }
+        assert_eq!(sets.count(), 1);
+        assert_eq!(sets.size(0), 100_000);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::util::grid::Grid;
+use std::{
+    cell::RefCell,
+    fmt::{self, Write},
+    fs, io, path,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread, time,
+};
+
+// The number of installed sinks over all threads, so `emit` can return
+// immediately without touching thread-local storage when nothing listens
+static SINKS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Magenta,
+    Dim,
+}
+
+// A snapshot of a solver's state, either a grid of characters or plain text,
+// with optional colours per cell and a caption
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    caption: String,
+    rows: Vec<Vec<(char, Option<Colour>)>>,
+}
+
+pub enum Sink {
+    // Replay the frames in the terminal at the given number of frames per second
+    Play {
+        fps: u32,
+    },
+    // Write every frame to a numbered text file in the directory
+    Dump {
+        directory: path::PathBuf,
+        count: usize,
+    },
+}
+
+impl Frame {
+    pub fn text(text: &str) -> Self {
+        let rows = text
+            .lines()
+            .map(|line| line.chars().map(|char| (char, None)).collect())
+            .collect();
+        Self {
+            caption: String::new(),
+            rows,
+        }
+    }
+
+    // Draw a grid with the first character of every cell
+    pub fn grid<T: fmt::Display>(grid: &Grid<T>) -> Self {
+        let rows = grid
+            .rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| (cell.to_string().chars().next().unwrap_or(' '), None))
+                    .collect()
+            })
+            .collect();
+        Self {
+            caption: String::new(),
+            rows,
+        }
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    // Colour the cell at the given position, ignoring positions outside the frame
+    pub fn paint(&mut self, x: usize, y: usize, colour: Colour) {
+        if let Some((_, cell_colour)) = self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell_colour = Some(colour);
+        }
+    }
+
+    pub fn to_plain(&self) -> String {
+        let mut plain = format!("{}\n", self.caption);
+        for row in &self.rows {
+            plain.extend(row.iter().map(|(char, _)| char));
+            plain.push('\n');
+        }
+        plain
+    }
+
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = format!("\x1b[1m{}\x1b[0m\n", self.caption);
+        for row in &self.rows {
+            for (char, colour) in row {
+                match colour {
+                    Some(colour) => write!(ansi, "\x1b[{}m{char}\x1b[0m", colour.code()).unwrap(),
+                    None => ansi.push(*char),
+                }
+            }
+            ansi.push('\n');
+        }
+        ansi
+    }
+}
+
+impl Colour {
+    fn code(&self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Magenta => 35,
+            Self::Dim => 2,
+        }
+    }
+}
+
+impl Sink {
+    fn show(&mut self, frame: &Frame) -> io::Result<()> {
+        match self {
+            Self::Play { fps } => {
+                // Clear the screen and move the cursor to the top left first
+                print!("\x1b[2J\x1b[H{}", frame.to_ansi());
+                thread::sleep(time::Duration::from_secs(1) / (*fps).max(1));
+                Ok(())
+            }
+            Self::Dump { directory, count } => {
+                *count += 1;
+                fs::create_dir_all(&*directory)?;
+                let path = directory.join(format!("frame{count:05}.txt"));
+                fs::write(path, frame.to_plain())
+            }
+        }
+    }
+}
+
+// Send frames emitted on this thread to the sink until `uninstall` is called
+pub fn install(sink: Sink) {
+    SINK.with(|current| {
+        if current.borrow_mut().replace(sink).is_none() {
+            SINKS.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+}
+
+pub fn uninstall() {
+    SINK.with(|current| {
+        if current.borrow_mut().take().is_some() {
+            SINKS.fetch_sub(1, Ordering::Relaxed);
+        }
+    });
+}
+
+// Whether frames are being collected, for solvers that need to keep extra
+// state around only for visualisation
+pub fn enabled() -> bool {
+    SINKS.load(Ordering::Relaxed) > 0 && SINK.with(|current| current.borrow().is_some())
+}
+
+// Build and show a frame, but only if a sink is installed on this thread
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if !enabled() {
+        return;
+    }
+    let frame = frame();
+    SINK.with(|current| {
+        if let Some(sink) = current.borrow_mut().as_mut() {
+            // Visualisation is a debugging aid, so it should never break a solver
+            if let Err(error) = sink.show(&frame) {
+                eprintln!("Could not show frame: {error}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_frames() {
+        assert_eq!(
+            Frame::grid(&Grid::parse("ab\ncd", Ok).unwrap()),
+            Frame::text("ab\ncd")
+        );
+        let mut frame = Frame::text("ab\ncd").caption("test");
+        frame.paint(1, 0, Colour::Red);
+        frame.paint(5, 5, Colour::Red);
+        assert_eq!(frame.to_plain(), "test\nab\ncd\n");
+        assert_eq!(
+            frame.to_ansi(),
+            "\x1b[1mtest\x1b[0m\na\x1b[31mb\x1b[0m\ncd\n"
+        );
+    }
+
+    #[test]
+    fn emit_without_sink() {
+        emit(|| panic!("frames should not be built without a sink"));
+    }
+
+    #[test]
+    fn dump_frames() {
+        let directory = std::env::temp_dir().join(format!("frames-{}", std::process::id()));
+        install(Sink::Dump {
+            directory: directory.clone(),
+            count: 0,
+        });
+        assert!(enabled());
+        emit(|| Frame::text("one"));
+        emit(|| Frame::text("two").caption("second"));
+        uninstall();
+        assert!(!enabled());
+        emit(|| Frame::text("three"));
+
+        assert_eq!(
+            fs::read_to_string(directory.join("frame00002.txt")).unwrap(),
+            "second\ntwo\n"
+        );
+        assert!(!directory.join("frame00003.txt").exists());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
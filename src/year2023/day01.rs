@@ -1,59 +1,57 @@
+use crate::solver::{self, Error, Records};
 use std::str;
+use tracing::instrument;
 
 const DIGITS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-pub fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> Result<usize, Error> {
     find_digits(input, false)
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> Result<usize, Error> {
     find_digits(input, true)
 }
 
-fn find_digits(input: &str, part_two: bool) -> usize {
-    input
-        .lines()
-        .map(|line| {
-            let mut digits = line.chars().enumerate().filter_map(|(i, char)| match char {
-                '0'..='9' => char.to_digit(10).map(|n| n as usize),
-                _ if part_two => DIGITS
-                    .iter()
-                    .enumerate()
-                    .find_map(|(n, name)| line[i..].starts_with(*name).then_some(n + 1)),
-                _ => None,
-            });
-
-            let first = digits.next().unwrap_or(0);
-            let last = digits.last().unwrap_or(first);
-
-            10 * first + last
-        })
-        .sum()
+pub fn model1(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input).into_iter().map(|line| {
+        (line.to_string(), calibration_value(line, false))
+    })))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
+pub fn model2(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input).into_iter().map(|line| {
+        (line.to_string(), calibration_value(line, true))
+    })))
+}
 
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day01a.txt"]
-            .iter()
-            .collect();
-        let input = fs::read_to_string(&path).unwrap();
+fn find_digits(input: &str, part_two: bool) -> Result<usize, Error> {
+    solver::checked_sum(
+        parse(input)
+            .into_iter()
+            .map(|line| Ok(calibration_value(line, part_two))),
+    )
+}
 
-        assert_eq!(find_digits(&input, false), 142)
-    }
+// the digits are found while solving, so parsing only splits the lines
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
 
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day01b.txt"]
+fn calibration_value(line: &str, part_two: bool) -> usize {
+    let mut digits = line.char_indices().filter_map(|(i, char)| match char {
+        '0'..='9' => char.to_digit(10).map(|n| n as usize),
+        _ if part_two => DIGITS
             .iter()
-            .collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(find_digits(&input, true), 281)
-    }
+            .enumerate()
+            .find_map(|(n, name)| line[i..].starts_with(*name).then_some(n + 1)),
+        _ => None,
+    });
+
+    let first = digits.next().unwrap_or(0);
+    let last = digits.next_back().unwrap_or(first);
+
+    10 * first + last
 }
@@ -1,62 +1,81 @@
+use crate::solver::{self, Error, Records};
+use crate::util::pattern::pattern;
 use std::str;
+use tracing::instrument;
 
+#[derive(Debug)]
 struct CubeGame {
     id: u32,
     cubes: Vec<[u32; 3]>,
 }
 
-pub fn part1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| CubeGame::parse(line).unwrap())
-        .filter(|cubegame| cubegame.is_possible([12, 13, 14]))
-        .map(|cubegame| cubegame.id)
-        .sum::<u32>() as usize
+pub fn part1(input: &str) -> Result<usize, Error> {
+    solver::checked_sum(parse(input)?.iter().map(|cubegame| {
+        Ok(if cubegame.is_possible([12, 13, 14]) {
+            cubegame.id as usize
+        } else {
+            0
+        })
+    }))
 }
 
-pub fn part2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| CubeGame::parse(line).unwrap())
-        .map(|cubegame| cubegame.power())
-        .sum::<u32>() as usize
+pub fn part2(input: &str) -> Result<usize, Error> {
+    solver::checked_sum(
+        parse(input)?
+            .iter()
+            .map(|cubegame| cubegame.power().ok_or(Error::Overflow)),
+    )
 }
 
-impl CubeGame {
-    fn parse(line: &str) -> Option<Self> {
-        let (id, game) = line.trim_start_matches("Game ").split_once(": ")?;
-        let id = id.parse().ok()?;
+pub fn model(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input)?))
+}
+
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse(input: &str) -> Result<Vec<CubeGame>, Error> {
+    input.lines().map(str::parse).collect()
+}
 
-        // process game
-        let cubes = game
-            .split("; ")
-            .map(|hand| {
-                let mut colours = [0, 0, 0];
-                hand.split(", ").for_each(|action| {
-                    let (n, colour) = action.split_once(' ').unwrap();
-                    let n = n.parse().unwrap();
-                    let colour_id = match colour {
-                        "red" => 0,
-                        "green" => 1,
-                        "blue" => 2,
-                        _ => panic!(),
-                    };
-                    colours[colour_id] = n;
-                });
-                colours
-            })
-            .collect();
+pattern!(CubeGame = "Game " {id} ": " {cubes: parse_cubes});
+
+// hands of cubes separated by "; ", as red, green and blue counts
+fn parse_cubes(game: &str) -> Result<Vec<[u32; 3]>, Error> {
+    game.split("; ")
+        .map(|hand| {
+            let mut colours = [0, 0, 0];
+            for action in hand.split(", ") {
+                let Draw { n, colour } = action.parse()?;
+                colours[colour] = n;
+            }
+            Ok(colours)
+        })
+        .collect()
+}
+
+struct Draw {
+    n: u32,
+    colour: usize,
+}
 
-        Some(CubeGame { id, cubes })
+pattern!(Draw = {n} " " {colour: colour_index});
+
+fn colour_index(colour: &str) -> Result<usize, Error> {
+    match colour {
+        "red" => Ok(0),
+        "green" => Ok(1),
+        "blue" => Ok(2),
+        _ => Err(Error::Parse(format!("unknown colour {colour:?}"))),
     }
+}
 
+impl CubeGame {
     fn is_possible(&self, colours: [u32; 3]) -> bool {
         self.cubes.iter().all(|&[red, green, blue]| {
             red <= colours[0] && green <= colours[1] && blue <= colours[2]
         })
     }
 
-    fn power(&self) -> u32 {
+    fn power(&self) -> Option<usize> {
         let minima = self.cubes.iter().fold([0, 0, 0], |minima, colours| {
             [
                 minima[0].max(colours[0]),
@@ -64,26 +83,8 @@ impl CubeGame {
                 minima[2].max(colours[2]),
             ]
         });
-        minima[0] * minima[1] * minima[2]
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
-
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day02.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 8)
-    }
-
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day02.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 2286)
+        (minima[0] as usize)
+            .checked_mul(minima[1] as usize)?
+            .checked_mul(minima[2] as usize)
     }
 }
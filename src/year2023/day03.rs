@@ -1,29 +1,38 @@
-use std::str;
+use crate::solver::{self, Error, Records};
+use crate::util::grid::Grid;
+use crate::util::point::Point2;
+use crate::visual::{self, Colour, Frame};
+use std::{fmt, str};
+use tracing::instrument;
 
 #[derive(Debug)]
 struct Schematic {
-    entries: Vec<Vec<Entry>>,
+    entries: Grid<Entry>,
 }
 
+// part numbers together with the position of an adjacent gear, if any
+type PartNumber = (u32, Option<Point2>);
+
 #[derive(Debug, Copy, Clone)]
 enum Entry {
     Number { value: u32 },
     Gear,
-    Symbol,
+    Symbol { char: char },
     Empty,
 }
 
-pub fn part1(input: &str) -> usize {
-    Schematic::parse(input)
-        .parts()
-        .iter()
-        .map(|(number, _)| number)
-        .sum::<u32>() as usize
+pub fn part1(input: &str) -> Result<usize, Error> {
+    solver::checked_sum(
+        Schematic::parse(input)?
+            .parts()?
+            .iter()
+            .map(|(number, _)| Ok(*number as usize)),
+    )
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> Result<usize, Error> {
     // generate list of parts and gears
-    let parts = Schematic::parse(input).parts();
+    let parts = Schematic::parse(input)?.parts()?;
     let gears = {
         let mut gears = parts
             .iter()
@@ -35,144 +44,151 @@ pub fn part2(input: &str) -> usize {
     };
 
     // iterate through the gears and calculate each gear ratio
-    gears
-        .iter()
-        .map(|gear| {
-            let gear_parts = parts
-                .iter()
-                .filter(|(_, part_gear)| *part_gear == Some(*gear))
-                .map(|(number, _)| number)
-                .collect::<Vec<_>>();
-            if gear_parts.len() == 2 {
-                gear_parts[0] * gear_parts[1]
-            } else {
-                0
-            }
-        })
-        .sum::<u32>() as usize
+    solver::checked_sum(gears.iter().map(|gear| {
+        let gear_parts = parts
+            .iter()
+            .filter(|(_, part_gear)| *part_gear == Some(*gear))
+            .map(|(number, _)| *number as usize)
+            .collect::<Vec<_>>();
+        if gear_parts.len() == 2 {
+            gear_parts[0]
+                .checked_mul(gear_parts[1])
+                .ok_or(Error::Overflow)
+        } else {
+            Ok(0)
+        }
+    }))
+}
+
+pub fn model(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(Schematic::parse(input)?.parts()?))
 }
 
 impl Schematic {
-    fn parse(input: &str) -> Self {
-        let entries = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|entry| match entry {
-                        '.' => Entry::Empty,
-                        '*' => Entry::Gear,
-                        '0'..='9' => Entry::Number {
-                            value: entry.to_digit(10).unwrap(),
-                        },
-                        _ => Entry::Symbol,
-                    })
-                    .collect()
+    #[instrument(name = "parse", level = "debug", skip_all)]
+    fn parse(input: &str) -> Result<Self, Error> {
+        let entries = Grid::parse(input, |entry| {
+            Ok(match entry {
+                '.' => Entry::Empty,
+                '*' => Entry::Gear,
+                _ => match entry.to_digit(10) {
+                    Some(value) => Entry::Number { value },
+                    None => Entry::Symbol { char: entry },
+                },
             })
-            .collect();
-        Schematic { entries }
+        })?;
+        Ok(Schematic { entries })
     }
 
+    // the entries around a horizontal number of length dx starting at (x, y)
     fn nbrs(&self, x: usize, y: usize, dx: usize) -> Vec<(Entry, usize, usize)> {
-        // generate neighbour offset coordinates
-        let offsets: Vec<(isize, isize)> = {
-            let mut offsets = vec![(-1, -1), (-1, 0), (-1, 1)];
-            for i in 0..dx {
-                offsets.append(&mut vec![(i as isize, -1), (i as isize, 1)]);
-            }
-            offsets.append(&mut vec![
-                (dx as isize, -1),
-                (dx as isize, 0),
-                (dx as isize, 1),
-            ]);
-            offsets
-        };
-
-        // save neighbour entries if they exist
-        let mut entries = Vec::new();
-        for (dx, dy) in offsets {
-            let x_nbr = x as isize + dx;
-            let y_nbr = y as isize + dy;
-
-            // convert coordinates to usize if possible
-            if x_nbr >= 0 && y_nbr >= 0 {
-                let x_nbr = x_nbr as usize;
-                let y_nbr = y_nbr as usize;
-
-                // save neighbours if they exist
-                if let Some(line) = self.entries.get(y_nbr) {
-                    if let Some(entry) = line.get(x_nbr) {
-                        entries.push((*entry, x_nbr, y_nbr));
-                    }
-                }
-            }
-        }
-        entries
+        let mut positions = (x..x + dx)
+            .flat_map(|x| self.entries.neighbours8(x, y))
+            .filter(|&(x_nbr, y_nbr)| y_nbr != y || !(x..x + dx).contains(&x_nbr))
+            .collect::<Vec<_>>();
+        positions.sort();
+        positions.dedup();
+        positions
+            .into_iter()
+            .map(|(x_nbr, y_nbr)| (self.entries[(x_nbr, y_nbr)], x_nbr, y_nbr))
+            .collect()
     }
 
-    fn parts(&self) -> Vec<(u32, Option<(usize, usize)>)> {
+    fn parts(&self) -> Result<Vec<PartNumber>, Error> {
         let mut numbers = Vec::new();
-        for y in 0..(self.entries.len()) {
+
+        // numbers found so far and whether they are part numbers, for visualisation
+        let mut highlights = Vec::new();
+
+        for y in 0..self.entries.height() {
             let mut skip = 0;
-            for x in 0..(self.entries[y].len()) {
+            for x in 0..self.entries.width() {
                 // skip if we are halfway through a number
                 if skip > 0 {
                     skip -= 1;
                 } else {
-                    let (number, dx) = self.construct_number(x, y);
+                    let (number, dx) = self.construct_number(x, y)?;
 
                     // number = 0 means it is not actually a number
                     if number > 0 {
                         let nbrs = self.nbrs(x, y, dx);
-                        if !nbrs.iter().all(|(nbr, _, _)| !matches!(nbr, Entry::Symbol)) {
+                        if visual::enabled() {
+                            let is_part = nbrs.iter().any(|(nbr, _, _)| {
+                                matches!(nbr, Entry::Symbol { .. } | Entry::Gear)
+                            });
+                            highlights.push((x, y, dx, is_part));
+                        }
+                        if !nbrs
+                            .iter()
+                            .all(|(nbr, _, _)| !matches!(nbr, Entry::Symbol { .. }))
+                        {
                             numbers.push((number, None));
                         } else {
-                            nbrs.iter().for_each(|(nbr, x_nbr, y_nbr)| {
+                            for (nbr, x_nbr, y_nbr) in nbrs {
                                 if matches!(nbr, Entry::Gear) {
-                                    numbers.push((number, Some((*x_nbr, *y_nbr))))
+                                    let gear = Point2::try_from((x_nbr, y_nbr))
+                                        .map_err(|_| Error::Overflow)?;
+                                    numbers.push((number, Some(gear)));
                                 }
-                            })
+                            }
                         }
                     }
                     skip = dx;
                 }
             }
+            visual::emit(|| {
+                self.frame(&highlights).caption(format!(
+                    "row {}/{}: {} part numbers",
+                    y + 1,
+                    self.entries.height(),
+                    numbers.len()
+                ))
+            });
         }
-        numbers
+        Ok(numbers)
     }
 
-    fn construct_number(&self, x: usize, y: usize) -> (u32, usize) {
-        let mut number = 0;
-        let mut dx = 0;
-        while let Entry::Number { value } = {
-            if let Some(entry) = self.entries[y].get(x + dx) {
-                entry
-            } else {
-                return (number, dx);
+    // draw the schematic with part numbers in green and other numbers in red
+    fn frame(&self, highlights: &[(usize, usize, usize, bool)]) -> Frame {
+        let mut frame = Frame::grid(&self.entries);
+        for ((x, y), entry) in self.entries.iter() {
+            match entry {
+                Entry::Gear => frame.paint(x, y, Colour::Magenta),
+                Entry::Symbol { .. } => frame.paint(x, y, Colour::Yellow),
+                Entry::Empty => frame.paint(x, y, Colour::Dim),
+                Entry::Number { .. } => (),
+            }
+        }
+        for &(x, y, dx, is_part) in highlights {
+            let colour = if is_part { Colour::Green } else { Colour::Red };
+            for i in 0..dx {
+                frame.paint(x + i, y, colour);
             }
-        } {
-            number = 10 * number + value;
-            dx += 1;
         }
-        (number, dx)
+        frame
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
 
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day03.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 4361)
+    fn construct_number(&self, x: usize, y: usize) -> Result<(u32, usize), Error> {
+        let mut number: u32 = 0;
+        let mut dx = 0;
+        while let Some(Entry::Number { value }) = self.entries.get(x + dx, y) {
+            number = number
+                .checked_mul(10)
+                .and_then(|number| number.checked_add(*value))
+                .ok_or(Error::Overflow)?;
+            dx += 1;
+        }
+        Ok((number, dx))
     }
+}
 
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day03.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 467835)
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Number { value } => write!(f, "{value}"),
+            Entry::Gear => write!(f, "*"),
+            Entry::Symbol { char } => write!(f, "{char}"),
+            Entry::Empty => write!(f, "."),
+        }
     }
 }
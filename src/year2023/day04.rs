@@ -1,57 +1,71 @@
+use crate::solver::{self, Error, Records};
+use crate::util::parse::Source;
 use std::{collections::VecDeque, str};
+use tracing::instrument;
 
-pub fn part1(input: &str) -> usize {
-    input.lines().map(wins).map(score).sum()
+#[derive(Debug)]
+struct Card {
+    winning: Vec<usize>,
+    have: Vec<usize>,
 }
 
-pub fn part2(input: &str) -> usize {
+pub fn part1(input: &str) -> Result<usize, Error> {
+    solver::checked_sum(parse(input)?.iter().map(|card| score(card.wins())))
+}
+
+pub fn part2(input: &str) -> Result<usize, Error> {
     let mut buf = VecDeque::new();
-    input.lines().map(wins).fold(0, |counter, wins| {
-        let cards = 1 + buf.pop_front().unwrap_or(0);
-        (0..wins).for_each(|i| match buf.get_mut(i) {
-            Some(n) => *n += cards,
-            None => buf.push_back(cards),
-        });
-        counter + cards
+    parse(input)?.iter().try_fold(0_usize, |counter, card| {
+        let wins = card.wins();
+        let cards = buf
+            .pop_front()
+            .unwrap_or(0_usize)
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+        for i in 0..wins {
+            match buf.get_mut(i) {
+                Some(n) => *n = n.checked_add(cards).ok_or(Error::Overflow)?,
+                None => buf.push_back(cards),
+            }
+        }
+        counter.checked_add(cards).ok_or(Error::Overflow)
     })
 }
 
-fn wins(line: &str) -> usize {
-    let (_, card) = line.split_once(": ").unwrap();
-    let (win, have) = card.split_once(" | ").unwrap();
-    let win = win
-        .split_whitespace()
-        .map(|n| n.parse::<usize>().unwrap())
-        .collect::<Vec<_>>();
-    have.split_whitespace()
-        .map(|n| n.parse().unwrap())
-        .filter(|entry| win.contains(entry))
-        .count()
+pub fn model(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input)?))
 }
 
-fn score(wins: usize) -> usize {
-    match wins as u32 {
-        0 => 0,
-        n => 2_usize.pow(n - 1),
-    }
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse(input: &str) -> Result<Vec<Card>, Error> {
+    let source = Source::new(input);
+    source
+        .lines()
+        .map(|line| Card::parse(&source, line))
+        .collect()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
+impl Card {
+    fn parse(source: &Source, line: &str) -> Result<Self, Error> {
+        let (_, card) = source.split_once(line, ": ")?;
+        let (winning, have) = source.split_once(card, " | ")?;
+        Ok(Card {
+            winning: source.unsigned(winning)?,
+            have: source.unsigned(have)?,
+        })
+    }
 
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day04.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 13)
+    fn wins(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|entry| self.winning.contains(entry))
+            .count()
     }
+}
 
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day04.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 30)
+fn score(wins: usize) -> Result<usize, Error> {
+    match wins as u32 {
+        0 => Ok(0),
+        n => 2_usize.checked_pow(n - 1).ok_or(Error::Overflow),
     }
 }
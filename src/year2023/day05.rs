@@ -1,90 +1,230 @@
+#[cfg(test)]
+use crate::check::Rng;
+use crate::solver::{Error, Records};
+use crate::util::interval::{Interval, IntervalSet};
+use crate::util::parse::Source;
+use crate::visual::{self, Frame};
 use itertools::Itertools;
 use std::str;
+use tracing::{debug, instrument};
 
-pub fn part1(input: &str) -> usize {
-    let (seeds, maps) = parse(input);
-    seeds
-        .map(|seed| {
-            maps.iter()
-                .fold(seed, |source, map| lookup(source, &map[..]))
-        })
-        .min()
-        .unwrap()
+type Map = Vec<[usize; 3]>;
+
+pub fn part1(input: &str) -> Result<usize, Error> {
+    let (seeds, maps) = parse(input)?;
+    lowest_location(seeds.into_iter(), &maps)
 }
 
-pub fn part2(input: &str) -> usize {
-    let (seeds, maps) = parse(input);
-    seeds
+pub fn part2(input: &str) -> Result<usize, Error> {
+    let (seeds, maps) = parse(input)?;
+    let mut ranges = seeds
+        .into_iter()
         .tuples()
-        .flat_map(|(start, len)| start..(start + len))
+        .map(|(start, len)| interval(start, len))
+        .collect::<Result<IntervalSet, Error>>()?;
+
+    // push whole ranges through every map, splitting them at the map's ranges
+    for (step, map) in maps.iter().enumerate() {
+        let table = map
+            .iter()
+            .map(|&[dest_start, source_start, len]| {
+                let source = interval(source_start, len)?;
+                let dest = interval(dest_start, len)?;
+                Ok((source, dest.start - source.start))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        ranges = ranges.map_ranges(&table);
+        visual::emit(|| {
+            let values = ranges
+                .intervals()
+                .iter()
+                .map(|r| format!("{r:?}"))
+                .join("\n");
+            Frame::text(&values).caption(format!("after map {}", step + 1))
+        });
+        debug!(step, ranges = ranges.intervals().len(), "mapped ranges");
+    }
+    let lowest = ranges.min().ok_or(Error::NoSolution)?;
+    usize::try_from(lowest).map_err(|_| Error::Overflow)
+}
+
+// The range of `len` values from `start`, which must all fit in an `i64`
+fn interval(start: usize, len: usize) -> Result<Interval, Error> {
+    let end = start.checked_add(len).ok_or(Error::Overflow)?;
+    let end = i64::try_from(end).map_err(|_| Error::Overflow)?;
+    Ok(Interval::new(end - len as i64, end))
+}
+
+pub fn model(input: &str) -> Result<Records, Error> {
+    let (seeds, maps) = parse(input)?;
+    let mut records: Records = vec![Box::new(seeds)];
+    records.extend(maps.into_iter().map(|map| Box::new(map) as _));
+    Ok(records)
+}
+
+fn lowest_location(seeds: impl Iterator<Item = usize>, maps: &[Map; 7]) -> Result<usize, Error> {
+    seeds
         .map(|seed| {
+            // show the value of the seed after every map
+            visual::emit(|| {
+                let values = maps
+                    .iter()
+                    .scan(seed, |source, map| {
+                        *source = lookup(*source, &map[..]).ok()?;
+                        Some(source.to_string())
+                    })
+                    .join("\n");
+                Frame::text(&values).caption(format!("seed {seed}"))
+            });
             maps.iter()
-                .fold(seed, |source, map| lookup(source, &map[..]))
+                .try_fold(seed, |source, map| lookup(source, &map[..]))
         })
-        .min()
-        .unwrap()
+        .process_results(|locations| locations.min())?
+        .ok_or(Error::NoSolution)
 }
 
-fn parse(input: &str) -> (impl Iterator<Item = usize> + '_, [Vec<[usize; 3]>; 7]) {
-    let mut blocks = input.split("\n\n").flat_map(|s| s.split("\r\n\r\n"));
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse(input: &str) -> Result<(Vec<usize>, [Map; 7]), Error> {
+    let source = Source::new(input);
+    let mut blocks = source.blocks();
     let seeds = blocks
         .next()
-        .unwrap()
-        .trim_start_matches("seeds: ")
-        .split_whitespace()
-        .map(|seed| seed.parse().unwrap());
+        .ok_or_else(|| source.error(input, "expected a line of seeds"))?;
+    let seeds: Vec<usize> = source.unsigned(source.strip_prefix(seeds, "seeds:")?)?;
 
-    let maps: [_; 7] = blocks
+    let maps = blocks
         .map(|block| {
             block
                 .lines()
                 .skip(1)
-                .map(|line| {
-                    let map_line: [usize; 3] = line
-                        .split_whitespace()
-                        .map(|num| num.parse().unwrap())
-                        .collect::<Vec<_>>()
-                        .try_into()
-                        .unwrap();
-                    map_line
-                })
-                .collect::<Vec<_>>()
+                .map(|line| source.array(line))
+                .collect::<Result<Vec<_>, _>>()
         })
-        .collect::<Vec<_>>()
+        .collect::<Result<Vec<_>, _>>()?;
+    let count = maps.len();
+    let maps: [_; 7] = maps
         .try_into()
-        .unwrap();
+        .map_err(|_| Error::Parse(format!("expected 7 maps, found {count}")))?;
 
-    (seeds, maps)
+    debug!(
+        seeds = seeds.len(),
+        map_lines = maps.iter().map(Vec::len).sum::<usize>(),
+        "parsed almanac"
+    );
+    Ok((seeds, maps))
 }
 
-fn lookup(source: usize, map: &[[usize; 3]]) -> usize {
+fn lookup(source: usize, map: &[[usize; 3]]) -> Result<usize, Error> {
     map.iter()
         .find_map(|&[dest_start, source_start, len]| {
-            if source >= source_start && source <= source_start + len {
-                Some(source - source_start + dest_start)
+            if source >= source_start && source - source_start < len {
+                Some(
+                    (source - source_start)
+                        .checked_add(dest_start)
+                        .ok_or(Error::Overflow),
+                )
             } else {
                 None
             }
         })
-        .unwrap_or(source)
+        .unwrap_or(Ok(source))
+}
+
+#[cfg(test)]
+pub fn part2_brute_force(input: &str) -> Result<usize, Error> {
+    let (seeds, maps) = parse(input)?;
+    let mut lowest = None;
+    for (start, len) in seeds.into_iter().tuples() {
+        for seed in start..start.checked_add(len).ok_or(Error::Overflow)? {
+            let location = maps
+                .iter()
+                .try_fold(seed, |source, map| lookup(source, &map[..]))?;
+            lowest = Some(lowest.map_or(location, |lowest: usize| lowest.min(location)));
+        }
+    }
+    lowest.ok_or(Error::NoSolution)
+}
+
+#[cfg(test)]
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let limit = 10 + 10 * size;
+    let seeds = (0..=size / 4)
+        .map(|_| format!("{} {}", rng.range(0, limit), rng.range(1, 2 + size)))
+        .join(" ");
+
+    // every map gets a few source ranges, which may touch or overlap
+    let maps = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ]
+    .iter()
+    .map(|name| {
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while start < limit && lines.len() <= size / 3 {
+            let source_start = (start + rng.range(0, limit / 4)).saturating_sub(rng.range(0, 3));
+            let len = rng.range(1, limit / 4);
+            lines.push(format!("{} {source_start} {len}", rng.range(0, limit)));
+            start = source_start + len;
+        }
+        format!("{name} map:\n{}", lines.join("\n"))
+    })
+    .join("\n\n");
+
+    format!("seeds: {seeds}\n\n{maps}")
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::{fs, path::PathBuf};
+
+    const MAP: [[usize; 3]; 2] = [[50, 98, 2], [52, 50, 48]];
+
+    #[test]
+    fn lookup_range_edges() {
+        // the first and last value of each range move, the values after do not
+        for (source, dest) in [(97, 99), (98, 50), (99, 51), (100, 100), (49, 49), (50, 52)] {
+            assert_eq!(lookup(source, &MAP), Ok(dest), "source {source}");
+        }
+        assert_eq!(lookup(0, &[[0, 0, 0]]), Ok(0));
+    }
 
     #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day05.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 35)
+    fn ranges_split_at_map_edges() {
+        let input = format!(
+            "seeds: 48 4 97 4 200 1\n\n{}",
+            (0..7)
+                .map(|i| format!(
+                    "map {i}:\n{}",
+                    if i == 0 { "50 98 2\n52 50 48" } else { "" }
+                ))
+                .join("\n\n")
+        );
+        // 48..52 maps to 48, 49, 52, 53 and 97..101 to 99, 50, 51, 100
+        assert_eq!(
+            part1(&input.replacen("48 4 97 4 200 1", "49 101", 1)),
+            Ok(49)
+        );
+        assert_eq!(part2(&input), Ok(48));
+        assert_eq!(part2(&input.replacen("48 4 97 4", "99 1 100 2", 1)), Ok(51));
+        assert_eq!(part2(&input), part2_brute_force(&input));
     }
 
     #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day05.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 46)
+    fn malformed_almanacs() {
+        let error = |input: &str| match parse(input) {
+            Err(Error::Parse(message)) => message,
+            other => panic!("expected a parse error, got {other:?}"),
+        };
+        assert_eq!(error(""), "line 1, column 1: expected a line of seeds");
+        assert_eq!(
+            error("seeds: 1 two 3"),
+            "line 1, column 10: expected usize, got \"two\""
+        );
     }
 }
@@ -1,59 +1,98 @@
+#[cfg(test)]
+use crate::check::Rng;
+use crate::solver::{self, Error, Records};
+use crate::util::{math, parse::Source};
+#[cfg(test)]
+use itertools::Itertools;
 use std::str;
+use tracing::{debug, instrument};
 
-pub fn part1(input: &str) -> usize {
-    let input = parse1(input);
-    input.map(|(t, s)| count_wins(t, s)).product()
+pub fn part1(input: &str) -> Result<usize, Error> {
+    parse1(input)?
+        .into_iter()
+        .try_fold(1_usize, |product, (t, s)| {
+            product
+                .checked_mul(count_wins(t, s)?)
+                .ok_or(Error::Overflow)
+        })
 }
 
-pub fn part2(input: &str) -> usize {
-    let (t, s) = parse2(input);
+pub fn part2(input: &str) -> Result<usize, Error> {
+    let (t, s) = parse2(input)?;
     count_wins(t, s)
 }
 
-fn parse1(input: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
-    let (times, distances) = input.split_once('\n').unwrap();
-    let times = times
-        .trim_start_matches("Time:")
-        .split_whitespace()
-        .map(|t| t.parse().unwrap());
-    let distances = distances
-        .trim_start_matches("Distance:")
-        .split_whitespace()
-        .map(|d| d.parse().unwrap());
-    times.zip(distances)
+pub fn model1(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse1(input)?))
 }
 
-fn parse2(input: &str) -> (usize, usize) {
-    let (time, dist) = input.split_once('\n').unwrap();
-    let mut time = time.to_string();
-    let mut dist = dist.to_string();
-    time.retain(|c| c.is_ascii_digit());
-    dist.retain(|c| c.is_ascii_digit());
-    let time = time.parse().unwrap();
-    let dist = dist.parse().unwrap();
-    (time, dist)
+pub fn model2(input: &str) -> Result<Records, Error> {
+    Ok(solver::records([parse2(input)?]))
 }
 
-fn count_wins(t: usize, s: usize) -> usize {
-    (0..=t).filter(|i| i * (t - i) > s).count()
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse1(input: &str) -> Result<Vec<(usize, usize)>, Error> {
+    let source = Source::new(input);
+    let (times, distances) = source.split_once(input, "\n")?;
+    let times: Vec<usize> = source.unsigned(source.strip_prefix(times, "Time:")?)?;
+    let distances = source.unsigned(source.strip_prefix(distances, "Distance:")?)?;
+    if times.len() != distances.len() {
+        return Err(Error::Parse(
+            "expected as many distances as times".to_string(),
+        ));
+    }
+    Ok(times.into_iter().zip(distances).collect())
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
-
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day06.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 288)
-    }
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse2(input: &str) -> Result<(usize, usize), Error> {
+    let invalid = || Error::Parse("expected a time and a distance line".to_string());
+    let (time, dist) = input.split_once('\n').ok_or_else(invalid)?;
+    let parse_line = |line: &str, prefix| {
+        let mut line = line.strip_prefix(prefix).ok_or_else(invalid)?.to_string();
+        line.retain(|c| !c.is_whitespace());
+        line.parse().map_err(|_| invalid())
+    };
+    let time = parse_line(time, "Time:")?;
+    let dist = parse_line(dist, "Distance:")?;
+    debug!(time, dist, "parsed race");
+    Ok((time, dist))
+}
 
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day06.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 71503)
+// holding for h ms wins if h * (t - h) > s, i.e. h^2 - t * h + s < 0
+fn count_wins(t: usize, s: usize) -> Result<usize, Error> {
+    let t = i64::try_from(t).map_err(|_| Error::Overflow)?;
+    let s = i64::try_from(s).map_err(|_| Error::Overflow)?;
+    match math::quadratic_below_zero(1, -t, s) {
+        Some((low, high)) => usize::try_from(high - low + 1).map_err(|_| Error::Overflow),
+        None => Ok(0),
     }
 }
+
+#[cfg(test)]
+pub fn part1_brute_force(input: &str) -> Result<usize, Error> {
+    Ok(parse1(input)?
+        .into_iter()
+        .map(|(t, s)| (0..=t).filter(|i| i * (t - i) > s).count())
+        .product())
+}
+
+#[cfg(test)]
+pub fn part2_brute_force(input: &str) -> Result<usize, Error> {
+    let (t, s) = parse2(input)?;
+    Ok((0..=t).filter(|i| i * (t - i) > s).count())
+}
+
+#[cfg(test)]
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    // keep the concatenated part two race short enough to brute force
+    let races = (0..=size.min(2))
+        .map(|_| {
+            let t = rng.range(0, 10 + 5 * size);
+            (t, rng.range(0, t * t / 4 + 1))
+        })
+        .collect::<Vec<_>>();
+    let times = races.iter().map(|(t, _)| format!(" {t:4}")).join("");
+    let distances = races.iter().map(|(_, s)| format!(" {s:4}")).join("");
+    format!("Time:    {times}\nDistance:{distances}")
+}
@@ -1,4 +1,6 @@
+use crate::solver::{self, Error, Records};
 use std::{cmp, str};
+use tracing::{debug, instrument};
 
 const CARD_ORDER: [char; 13] = [
     '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
@@ -25,44 +27,52 @@ struct Hand {
     hand_type: HandType,
 }
 
-pub fn part1(input: &str) -> usize {
-    let hands = input
-        .lines()
-        .map(|line| parse(line, false))
-        .collect::<Vec<_>>();
-    calculate_score(hands)
+pub fn part1(input: &str) -> Result<usize, Error> {
+    calculate_score(parse(input, false)?)
 }
 
-pub fn part2(input: &str) -> usize {
-    let hands = input
-        .lines()
-        .map(|line| parse(line, true))
-        .collect::<Vec<_>>();
-    calculate_score(hands)
+pub fn part2(input: &str) -> Result<usize, Error> {
+    calculate_score(parse(input, true)?)
 }
 
-fn calculate_score(mut hands: Vec<(Hand, usize)>) -> usize {
-    hands.sort_by(|(hand_a, _), (hand_b, _)| hand_a.cmp(hand_b));
+pub fn model1(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input, false)?))
+}
+
+pub fn model2(input: &str) -> Result<Records, Error> {
+    Ok(solver::records(parse(input, true)?))
+}
+
+fn calculate_score(mut hands: Vec<(Hand, usize)>) -> Result<usize, Error> {
+    hands.sort_by_key(|(hand, _)| *hand);
+    debug!(hands = hands.len(), strongest = ?hands.last(), "ranked hands");
+
+    solver::checked_sum(
+        hands
+            .iter()
+            .map(|(_, bid)| bid)
+            .enumerate()
+            .map(|(rankm1, bid)| (rankm1 + 1).checked_mul(*bid).ok_or(Error::Overflow)),
+    )
+}
 
-    hands
-        .iter()
-        .map(|(_, bid)| bid)
-        .enumerate()
-        .map(|(rankm1, bid)| (rankm1 + 1) * bid)
-        .sum()
+#[instrument(name = "parse", level = "debug", skip_all)]
+fn parse(input: &str, jokers: bool) -> Result<Vec<(Hand, usize)>, Error> {
+    input.lines().map(|line| parse_line(line, jokers)).collect()
 }
 
-fn parse(line: &str, jokers: bool) -> (Hand, usize) {
-    let (hand, bid) = line.split_once(' ').unwrap();
-    let hand = process_hand(hand, jokers);
+fn parse_line(line: &str, jokers: bool) -> Result<(Hand, usize), Error> {
+    let invalid = || Error::Parse(format!("invalid hand {line:?}"));
+    let (hand, bid) = line.split_once(' ').ok_or_else(invalid)?;
+    let hand = process_hand(hand, jokers).ok_or_else(invalid)?;
 
-    let bid = bid.parse().unwrap();
-    (hand, bid)
+    let bid = bid.parse().map_err(|_| invalid())?;
+    Ok((hand, bid))
 }
 
-fn process_hand(hand: &str, jokers: bool) -> Hand {
+fn process_hand(hand: &str, jokers: bool) -> Option<Hand> {
+    let strengths = strengths(hand, jokers)?;
     let hand_type = hand_type(hand);
-    let strengths = strengths(hand, jokers);
     let hand_type = {
         if jokers {
             let joker_count = count_jokers(hand);
@@ -71,10 +81,10 @@ fn process_hand(hand: &str, jokers: bool) -> Hand {
             hand_type
         }
     };
-    Hand {
+    Some(Hand {
         strengths,
         hand_type,
-    }
+    })
 }
 
 fn map_hand_type(hand_type: HandType, joker_count: usize) -> HandType {
@@ -121,7 +131,8 @@ fn hand_type(hand: &str) -> HandType {
     }
 }
 
-fn strengths(hand: &str, jokers: bool) -> [usize; 5] {
+// returns None unless the hand consists of exactly five valid cards
+fn strengths(hand: &str, jokers: bool) -> Option<[usize; 5]> {
     hand.chars()
         .map(|card| {
             {
@@ -133,11 +144,10 @@ fn strengths(hand: &str, jokers: bool) -> [usize; 5] {
             }
             .iter()
             .position(|&x| x == card)
-            .unwrap()
         })
-        .collect::<Vec<_>>()
+        .collect::<Option<Vec<_>>>()?
         .try_into()
-        .unwrap()
+        .ok()
 }
 
 impl cmp::PartialOrd for Hand {
@@ -154,23 +164,3 @@ impl cmp::Ord for Hand {
         }
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use std::{fs, path::PathBuf};
-
-    #[test]
-    fn test1() {
-        let path: PathBuf = [".", "input", "test", "2023", "day07.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part1(&input), 6440)
-    }
-
-    #[test]
-    fn test2() {
-        let path: PathBuf = [".", "input", "test", "2023", "day07.txt"].iter().collect();
-        let input = fs::read_to_string(&path).unwrap();
-        assert_eq!(part2(&input), 5905)
-    }
-}